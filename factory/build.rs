@@ -0,0 +1,9 @@
+use gear_wasm_builder::WasmBuilder;
+use gmeta::Metadata;
+use rps_factory_io::FactoryMetadata;
+
+fn main() {
+    WasmBuilder::with_meta(FactoryMetadata::repr())
+        .exclude_features(["binary-vendor"])
+        .build();
+}
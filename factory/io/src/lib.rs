@@ -0,0 +1,131 @@
+#![no_std]
+
+use gmeta::{In, InOut, Metadata};
+use gstd::{prelude::*, ActorId, CodeId};
+use rps_io::{ConfigError, GameConfig};
+
+pub struct FactoryMetadata;
+
+impl Metadata for FactoryMetadata {
+    /// The code id of the uploaded `rock-paper-scissors` program.
+    type Init = In<CodeId>;
+    type Handle = InOut<FactoryAction, Result<FactoryEvent, FactoryError>>;
+    type Reply = ();
+    type Others = ();
+    type Signal = ();
+    type State = FactoryState;
+}
+
+/// Every action replies with `Result<FactoryEvent, FactoryError>`.
+/// On failure it replies `Err(FactoryError)` and the value sent with the message is returned.
+///
+/// The deployed instances send `rps_io::GameOverNotification` to the factory when their game ends,
+/// such messages are told apart by the source.
+/// The value of the instances' replies, e.g. the withdrawn surplus, is sent on to the owner of the factory.
+#[derive(Debug, Decode, Encode, TypeInfo)]
+pub enum FactoryAction {
+    /// Deploys a new `rock-paper-scissors` program for one lobby.
+    /// The factory becomes the owner of the instance and subscribes to its game over notifications.
+    ///
+    /// # Arguments:
+    /// * `GameConfig`: is the config the instance is initialized with.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the factory.
+    /// * The `GameConfig` must be valid, see `GameConfig::validate`.
+    ///
+    /// On success replies `Ok(FactoryEvent::InstanceCreated(ActorId))` where `ActorId` is the address of the instance
+    /// and returns the value sent with the message.
+    CreateInstance(GameConfig),
+
+    /// Stops the lobby of the finished instance, so the players who have registered
+    /// for the next game get their bets back, pauses the instance, so nobody can register anymore,
+    /// and stops tracking its games.
+    /// The players can still claim their winnings and refunds from the instance.
+    /// The instance can be retired while its next game is running, then the game is stopped
+    /// and its pot is split among the remaining players, see `rps_io::Action::StopGame`.
+    ///
+    /// # Arguments:
+    /// * `ActorId`: is the address of the instance.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the factory.
+    /// * The instance must be in the `InstanceStatus::Finished` status.
+    ///
+    /// On success replies `Ok(FactoryEvent::InstanceRetired(ActorId))`.
+    RetireInstance(ActorId),
+
+    /// Sends `rps_io::Action::WithdrawFees` to the instance, so it sends the collected fees to their treasuries.
+    /// The result of the withdrawal isn't waited for.
+    ///
+    /// # Arguments:
+    /// * `ActorId`: is the address of the instance.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the factory.
+    /// * The instance must be deployed by the factory.
+    ///
+    /// On success replies `Ok(FactoryEvent::FeesWithdrawalRequested(ActorId))`.
+    WithdrawFees(ActorId),
+
+    /// Sends `rps_io::Action::WithdrawSurplus` to the instance.
+    /// The surplus the instance replies with is sent on to the owner of the factory.
+    /// The result of the withdrawal isn't waited for.
+    ///
+    /// # Arguments:
+    /// * `ActorId`: is the address of the instance.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the factory.
+    /// * The instance must be deployed by the factory.
+    ///
+    /// On success replies `Ok(FactoryEvent::SurplusWithdrawalRequested(ActorId))`.
+    WithdrawSurplus(ActorId),
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo)]
+pub enum FactoryEvent {
+    InstanceCreated(ActorId),
+    InstanceRetired(ActorId),
+    FeesWithdrawalRequested(ActorId),
+    SurplusWithdrawalRequested(ActorId),
+}
+
+/// Errors the factory replies with instead of `FactoryEvent` when an action fails.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum FactoryError {
+    /// The action is allowed only for the owner of the factory.
+    NotOwner,
+    /// The config of the new instance is invalid.
+    InvalidConfig(ConfigError),
+    /// The program couldn't be created from the stored code id.
+    ProgramCreationFailed,
+    /// The factory hasn't deployed such instance.
+    InstanceNotFound,
+    /// The instance hasn't finished a game yet or has already been retired.
+    InstanceNotFinished,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum InstanceStatus {
+    /// The first game of the instance hasn't ended yet.
+    Active,
+    /// At least one game of the instance has ended.
+    Finished { last_winner: Option<ActorId> },
+    /// The lobby of the instance has been stopped by the owner, its notifications are ignored.
+    Retired,
+}
+
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct InstanceInfo {
+    pub config: GameConfig,
+    pub status: InstanceStatus,
+    pub games_played: u32,
+}
+
+#[derive(Debug, Default, Encode, Decode, TypeInfo)]
+pub struct FactoryState {
+    pub owner: ActorId,
+    pub rps_code_id: CodeId,
+    pub instances: Vec<(ActorId, InstanceInfo)>,
+}
@@ -0,0 +1,207 @@
+use gmeta::Metadata;
+use gstd::{
+    debug, errors::Result as GstdResult, exec, msg, prelude::*, prog::ProgramGenerator, ActorId,
+    CodeId, MessageId,
+};
+use hashbrown::HashMap;
+use rps_factory_io::*;
+use rps_io::{Action, GameConfig, GameOverNotification, INIT_GAME_ID};
+
+static mut FACTORY: Option<Factory> = None;
+
+#[derive(Debug, Default)]
+pub struct Factory {
+    pub owner: ActorId,
+    pub rps_code_id: CodeId,
+    pub instances: HashMap<ActorId, InstanceInfo>,
+}
+
+impl Factory {
+    fn create_instance(&mut self, config: GameConfig) -> Result<(), FactoryError> {
+        self.validate_source_is_owner()?;
+        config.validate().map_err(FactoryError::InvalidConfig)?;
+
+        let (_, instance) = ProgramGenerator::create_program(self.rps_code_id, config.encode(), 0)
+            .map_err(|_| FactoryError::ProgramCreationFailed)?;
+        msg::send(
+            instance,
+            Action::SetGameOverListener(INIT_GAME_ID, Some(exec::program_id())),
+            0,
+        )
+        .expect("Can't subscribe to the instance");
+
+        self.instances.insert(
+            instance,
+            InstanceInfo {
+                config,
+                status: InstanceStatus::Active,
+                games_played: 0,
+            },
+        );
+
+        reply_ok(FactoryEvent::InstanceCreated(instance), msg::value());
+
+        Ok(())
+    }
+
+    fn retire_instance(&mut self, instance: ActorId) -> Result<(), FactoryError> {
+        self.validate_source_is_owner()?;
+
+        let info = self
+            .instances
+            .get_mut(&instance)
+            .ok_or(FactoryError::InstanceNotFound)?;
+        if !matches!(info.status, InstanceStatus::Finished { .. }) {
+            return Err(FactoryError::InstanceNotFinished);
+        }
+
+        msg::send(instance, Action::StopGame(INIT_GAME_ID), 0).expect("Can't stop the instance");
+        msg::send(instance, Action::Pause, 0).expect("Can't pause the instance");
+        info.status = InstanceStatus::Retired;
+
+        reply_ok(FactoryEvent::InstanceRetired(instance), 0);
+
+        Ok(())
+    }
+
+    fn withdraw_fees(&mut self, instance: ActorId) -> Result<(), FactoryError> {
+        self.validate_source_is_owner()?;
+        self.validate_instance_exists(&instance)?;
+
+        msg::send(instance, Action::WithdrawFees(INIT_GAME_ID), 0)
+            .expect("Can't withdraw the fees");
+
+        reply_ok(FactoryEvent::FeesWithdrawalRequested(instance), 0);
+
+        Ok(())
+    }
+
+    fn withdraw_surplus(&mut self, instance: ActorId) -> Result<(), FactoryError> {
+        self.validate_source_is_owner()?;
+        self.validate_instance_exists(&instance)?;
+
+        msg::send(instance, Action::WithdrawSurplus, 0).expect("Can't withdraw the surplus");
+
+        reply_ok(FactoryEvent::SurplusWithdrawalRequested(instance), 0);
+
+        Ok(())
+    }
+
+    fn game_over(&mut self, instance: ActorId, notification: GameOverNotification) {
+        let Some(info) = self.instances.get_mut(&instance) else {
+            return;
+        };
+
+        if info.status != InstanceStatus::Retired {
+            info.status = InstanceStatus::Finished {
+                last_winner: notification.winner,
+            };
+            info.games_played += 1;
+        }
+    }
+
+    fn validate_source_is_owner(&self) -> Result<(), FactoryError> {
+        if msg::source() != self.owner {
+            return Err(FactoryError::NotOwner);
+        }
+
+        Ok(())
+    }
+
+    fn validate_instance_exists(&self, instance: &ActorId) -> Result<(), FactoryError> {
+        if !self.instances.contains_key(instance) {
+            return Err(FactoryError::InstanceNotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[no_mangle]
+extern "C" fn init() {
+    let rps_code_id: CodeId = msg::load().expect("Could not load CodeId");
+    debug!("init(): {:?}", rps_code_id);
+
+    let factory = Factory {
+        owner: msg::source(),
+        rps_code_id,
+        ..Default::default()
+    };
+
+    unsafe { FACTORY = Some(factory) };
+}
+
+#[no_mangle]
+extern "C" fn handle() {
+    let factory: &mut Factory = unsafe { FACTORY.get_or_insert(Factory::default()) };
+    let source = msg::source();
+
+    if factory.instances.contains_key(&source) {
+        let notification: GameOverNotification =
+            msg::load().expect("Could not load GameOverNotification");
+        factory.game_over(source, notification);
+
+        return;
+    }
+
+    let action: FactoryAction = msg::load().expect("Could not load FactoryAction");
+
+    let result = match action {
+        FactoryAction::CreateInstance(config) => factory.create_instance(config),
+        FactoryAction::RetireInstance(instance) => factory.retire_instance(instance),
+        FactoryAction::WithdrawFees(instance) => factory.withdraw_fees(instance),
+        FactoryAction::WithdrawSurplus(instance) => factory.withdraw_surplus(instance),
+    };
+
+    if let Err(error) = result {
+        msg::reply(Err::<FactoryEvent, _>(error), msg::value()).expect("Can't send reply");
+    }
+}
+
+#[no_mangle]
+extern "C" fn handle_reply() {
+    let factory = static_mut_state();
+    let value = msg::value();
+
+    // The factory owns the instances, so the value they reply with, e.g. the withdrawn surplus,
+    // belongs to the owner of the factory.
+    if value != 0 && factory.instances.contains_key(&msg::source()) {
+        msg::send_bytes(factory.owner, [], value).expect("Can't forward the value");
+    }
+}
+
+fn reply_ok(event: FactoryEvent, value: u128) {
+    msg::reply(Ok::<_, FactoryError>(event), value).expect("Can't send reply");
+}
+
+fn common_state() -> <FactoryMetadata as Metadata>::State {
+    let Factory {
+        owner,
+        rps_code_id,
+        instances,
+    } = static_mut_state();
+
+    FactoryState {
+        owner: *owner,
+        rps_code_id: *rps_code_id,
+        instances: instances
+            .iter()
+            .map(|(instance, info)| (*instance, info.clone()))
+            .collect(),
+    }
+}
+
+fn static_mut_state() -> &'static Factory {
+    unsafe { FACTORY.get_or_insert(Default::default()) }
+}
+
+#[no_mangle]
+extern "C" fn state() {
+    reply(common_state()).expect(
+        "Failed to encode or reply with `<FactoryMetadata as Metadata>::State` from `state()`",
+    );
+}
+
+fn reply(payload: impl Encode) -> GstdResult<MessageId> {
+    msg::reply(payload, 0)
+}
@@ -0,0 +1,7 @@
+#![no_std]
+
+#[cfg(not(feature = "binary-vendor"))]
+mod contract;
+
+#[cfg(feature = "binary-vendor")]
+include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
@@ -0,0 +1,357 @@
+use gstd::{ActorId, Encode};
+use gtest::{Program, RunResult, System};
+use rps_factory_io::*;
+use rps_io::*;
+
+const OWNER: u64 = 2;
+const USERS: &[u64] = &[3, 4, 5];
+const TREASURY: u64 = 6;
+const BET: u128 = 1_000_000;
+const START_BALANCE: u128 = 1_000_000_000;
+const TIMEOUT: u64 = 5_000;
+const PASSWORD: &str = "pass12";
+const CONFIG: GameConfig = GameConfig {
+    bet_size: BET,
+    players_count_limit: 5,
+    allowlist: None,
+    min_players_to_start: 2,
+    auto_start: false,
+    entry_timeout_ms: TIMEOUT,
+    move_timeout_ms: TIMEOUT,
+    reveal_timeout_ms: TIMEOUT,
+    rematch_timeout_ms: 0,
+    block_timeouts: None,
+    block_time_ms: 1_000,
+    registration_opens_at: None,
+    starts_at: None,
+    abandon_policy: AbandonPolicy::Prolong,
+    fee_bps: 0,
+    treasury: ActorId::zero(),
+};
+
+const TARGET_DIR: &str = if cfg!(debug_assertions) {
+    "../target/wasm32-unknown-unknown/debug"
+} else {
+    "../target/wasm32-unknown-unknown/release"
+};
+
+fn init_factory(sys: &System) -> Program {
+    sys.init_logger();
+    USERS
+        .iter()
+        .copied()
+        .chain([OWNER])
+        .for_each(|id| sys.mint_to(id, START_BALANCE));
+
+    let rps_code_id = sys.submit_code(format!("{TARGET_DIR}/rock_paper_scissors.opt.wasm"));
+    let factory = Program::from_file(sys, format!("{TARGET_DIR}/rps_factory.opt.wasm"));
+    let result = factory.send(OWNER, rps_code_id);
+    assert!(!result.main_failed());
+
+    factory
+}
+
+fn instances(factory: &Program) -> Vec<ActorId> {
+    let state: FactoryState = factory.read_state().expect("Not suitable reply");
+
+    state
+        .instances
+        .into_iter()
+        .map(|(instance, _)| instance)
+        .collect()
+}
+
+fn create_instance(factory: &Program, config: GameConfig) -> ActorId {
+    let known_instances = instances(factory);
+    let result = factory.send(OWNER, FactoryAction::CreateInstance(config));
+
+    let instance = instances(factory)
+        .into_iter()
+        .find(|instance| !known_instances.contains(instance))
+        .expect("Instance not found");
+    assert!(result.contains(&(
+        OWNER,
+        Ok::<_, FactoryError>(FactoryEvent::InstanceCreated(instance)).encode()
+    )));
+
+    instance
+}
+
+fn instance_info(factory: &Program, instance: ActorId) -> InstanceInfo {
+    let state: FactoryState = factory.read_state().expect("Not suitable reply");
+
+    state
+        .instances
+        .into_iter()
+        .find(|(id, _)| *id == instance)
+        .map(|(_, info)| info)
+        .expect("Instance not found")
+}
+
+fn play_game(sys: &System, game: &Program, moves: &[Move]) -> RunResult {
+    for user in USERS {
+        game.send_with_value(*user, Action::Register(INIT_GAME_ID), BET);
+    }
+    sys.spend_blocks((TIMEOUT / 1_000 + 1) as u32);
+
+    let state: ContractState = game.read_state().expect("Not suitable reply");
+    let round = state.game(INIT_GAME_ID).expect("Game not found").round;
+    let real_moves: Vec<_> = moves
+        .iter()
+        .map(|users_move| format!("{}{PASSWORD}", users_move.clone() as u8))
+        .collect();
+
+    for (user, real_move) in USERS.iter().zip(&real_moves) {
+        let preimage =
+            commitment_preimage(real_move.as_bytes(), &(*user).into(), INIT_GAME_ID, round);
+        let hash = sp_core_hashing::blake2_256(&preimage);
+        game.send(*user, Action::MakeMove(INIT_GAME_ID, hash.to_vec()));
+    }
+
+    let (last, users) = USERS.split_last().expect("No users");
+    for (user, real_move) in users.iter().zip(&real_moves) {
+        game.send(
+            *user,
+            Action::Reveal(INIT_GAME_ID, real_move.as_bytes().to_vec()),
+        );
+    }
+
+    game.send(
+        *last,
+        Action::Reveal(
+            INIT_GAME_ID,
+            real_moves[USERS.len() - 1].as_bytes().to_vec(),
+        ),
+    )
+}
+
+#[test]
+fn create_instance_with_factory() {
+    let sys = System::new();
+    let factory = init_factory(&sys);
+
+    let result = factory.send(USERS[0], FactoryAction::CreateInstance(CONFIG));
+    assert!(result.contains(&(
+        USERS[0],
+        Err::<FactoryEvent, _>(FactoryError::NotOwner).encode()
+    )));
+
+    let invalid_config = GameConfig {
+        players_count_limit: 1,
+        ..CONFIG
+    };
+    let result = factory.send(OWNER, FactoryAction::CreateInstance(invalid_config));
+    assert!(result.contains(&(
+        OWNER,
+        Err::<FactoryEvent, _>(FactoryError::InvalidConfig(
+            ConfigError::PlayersCountLimitTooLow
+        ))
+        .encode()
+    )));
+
+    let instance = create_instance(&factory, CONFIG);
+    let info = instance_info(&factory, instance);
+    assert_eq!(info.status, InstanceStatus::Active);
+    assert_eq!(info.config, CONFIG);
+
+    let state: ContractState = sys
+        .get_program(instance)
+        .read_state()
+        .expect("Not suitable reply");
+    let game = state.game(INIT_GAME_ID).expect("Game not found");
+    assert_eq!(state.owner, factory.id());
+    assert_eq!(game.game_over_listener, Some(factory.id()));
+    assert_eq!(game.game_config, CONFIG);
+}
+
+#[test]
+fn game_over_notification() {
+    let sys = System::new();
+    let factory = init_factory(&sys);
+    let instance = create_instance(&factory, CONFIG);
+    let other_instance = create_instance(&factory, CONFIG);
+
+    let result = play_game(
+        &sys,
+        &sys.get_program(instance),
+        &[Move::Rock, Move::Rock, Move::Paper],
+    );
+    assert!(result.contains(&(
+        USERS[2],
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::GameOver {
+            winner: USERS[2].into()
+        }))
+        .encode()
+    )));
+
+    let info = instance_info(&factory, instance);
+    assert_eq!(
+        info.status,
+        InstanceStatus::Finished {
+            last_winner: Some(USERS[2].into())
+        }
+    );
+    assert_eq!(info.games_played, 1);
+    assert_eq!(
+        instance_info(&factory, other_instance).status,
+        InstanceStatus::Active
+    );
+}
+
+#[test]
+fn retire_instance() {
+    let sys = System::new();
+    let factory = init_factory(&sys);
+    let instance = create_instance(&factory, CONFIG);
+
+    let result = factory.send(OWNER, FactoryAction::RetireInstance(instance));
+    assert!(result.contains(&(
+        OWNER,
+        Err::<FactoryEvent, _>(FactoryError::InstanceNotFinished).encode()
+    )));
+
+    let result = factory.send(OWNER, FactoryAction::RetireInstance(USERS[0].into()));
+    assert!(result.contains(&(
+        OWNER,
+        Err::<FactoryEvent, _>(FactoryError::InstanceNotFound).encode()
+    )));
+
+    play_game(
+        &sys,
+        &sys.get_program(instance),
+        &[Move::Rock, Move::Rock, Move::Paper],
+    );
+
+    // the player registered for the next game gets the bet back
+    let game = sys.get_program(instance);
+    game.send_with_value(USERS[0], Action::Register(INIT_GAME_ID), BET);
+
+    let result = factory.send(USERS[0], FactoryAction::RetireInstance(instance));
+    assert!(result.contains(&(
+        USERS[0],
+        Err::<FactoryEvent, _>(FactoryError::NotOwner).encode()
+    )));
+
+    let result = factory.send(OWNER, FactoryAction::RetireInstance(instance));
+    assert!(result.contains(&(
+        OWNER,
+        Ok::<_, FactoryError>(FactoryEvent::InstanceRetired(instance)).encode()
+    )));
+    assert_eq!(
+        instance_info(&factory, instance).status,
+        InstanceStatus::Retired
+    );
+
+    let state: ContractState = game.read_state().expect("Not suitable reply");
+    assert!(state.paused);
+    assert!(state
+        .game(INIT_GAME_ID)
+        .expect("Game not found")
+        .lobby
+        .is_empty());
+
+    // nobody can register in the retired instance, but the refund can still be claimed
+    let result = game.send_with_value(USERS[1], Action::Register(INIT_GAME_ID), BET);
+    assert!(result.contains(&(USERS[1], Err::<Event, _>(RpsError::Paused).encode())));
+    let result = game.send(USERS[0], Action::Claim(INIT_GAME_ID));
+    assert!(result.contains(&(USERS[0], Ok::<_, RpsError>(Event::Claimed(BET)).encode())));
+}
+
+#[test]
+fn create_instance_returns_value() {
+    let sys = System::new();
+    let factory = init_factory(&sys);
+
+    factory.send_with_value(OWNER, FactoryAction::CreateInstance(CONFIG), BET);
+    sys.claim_value_from_mailbox(OWNER);
+
+    assert_eq!(sys.balance_of(OWNER), START_BALANCE);
+}
+
+#[test]
+fn withdraw_through_factory() {
+    let sys = System::new();
+    let factory = init_factory(&sys);
+    let instance = create_instance(
+        &factory,
+        GameConfig {
+            fee_bps: 1_000,
+            treasury: TREASURY.into(),
+            ..CONFIG
+        },
+    );
+    let game = sys.get_program(instance);
+    let fee = BET * 3 / 10;
+    let mistaken_value = 700;
+
+    play_game(&sys, &game, &[Move::Rock, Move::Rock, Move::Paper]);
+    game.send_with_value(USERS[2], Action::Claim(INIT_GAME_ID), mistaken_value);
+
+    for action in [
+        FactoryAction::WithdrawFees(instance),
+        FactoryAction::WithdrawSurplus(instance),
+    ] {
+        let result = factory.send(USERS[0], action);
+        assert!(result.contains(&(
+            USERS[0],
+            Err::<FactoryEvent, _>(FactoryError::NotOwner).encode()
+        )));
+    }
+    let result = factory.send(OWNER, FactoryAction::WithdrawFees(USERS[0].into()));
+    assert!(result.contains(&(
+        OWNER,
+        Err::<FactoryEvent, _>(FactoryError::InstanceNotFound).encode()
+    )));
+
+    let result = factory.send(OWNER, FactoryAction::WithdrawFees(instance));
+    assert!(result.contains(&(
+        OWNER,
+        Ok::<_, FactoryError>(FactoryEvent::FeesWithdrawalRequested(instance)).encode()
+    )));
+    sys.claim_value_from_mailbox(TREASURY);
+    assert_eq!(sys.balance_of(TREASURY), fee);
+
+    // the surplus comes to the factory and is sent on to its owner
+    let result = factory.send(OWNER, FactoryAction::WithdrawSurplus(instance));
+    assert!(result.contains(&(
+        OWNER,
+        Ok::<_, FactoryError>(FactoryEvent::SurplusWithdrawalRequested(instance)).encode()
+    )));
+    sys.claim_value_from_mailbox(OWNER);
+    assert_eq!(sys.balance_of(OWNER), START_BALANCE + mistaken_value);
+}
+
+#[test]
+fn retire_instance_during_game() {
+    let sys = System::new();
+    let factory = init_factory(&sys);
+    let instance = create_instance(&factory, CONFIG);
+    let game = sys.get_program(instance);
+
+    play_game(&sys, &game, &[Move::Rock, Move::Rock, Move::Paper]);
+
+    // the next game is in progress
+    for user in &USERS[0..2] {
+        game.send_with_value(*user, Action::Register(INIT_GAME_ID), BET);
+    }
+    sys.spend_blocks((TIMEOUT / 1_000 + 1) as u32);
+    game.send(USERS[0], Action::AdvanceStage(INIT_GAME_ID));
+
+    let result = factory.send(OWNER, FactoryAction::RetireInstance(instance));
+    assert!(result.contains(&(
+        OWNER,
+        Ok::<_, FactoryError>(FactoryEvent::InstanceRetired(instance)).encode()
+    )));
+
+    // the pot of the stopped game is split between its players
+    let state: ContractState = game.read_state().expect("Not suitable reply");
+    let game_state = state.game(INIT_GAME_ID).expect("Game not found");
+    assert!(state.paused);
+    assert!(matches!(game_state.stage, GameStage::Preparation));
+    assert_eq!(game_state.pot, 0);
+    for user in &USERS[0..2] {
+        assert!(game_state
+            .pending_withdrawals
+            .contains(&((*user).into(), BET)));
+    }
+}
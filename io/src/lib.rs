@@ -5,9 +5,27 @@ use gstd::{prelude::*, ActorId};
 
 pub struct ContractMetadata;
 
+pub type GameId = u64;
+
+/// The id of the game created on the program initialization.
+pub const INIT_GAME_ID: GameId = 0;
+
+const MIN_TIMEOUT_MS: u64 = 5000;
+/// A stage can't last longer than a week.
+const MAX_TIMEOUT_MS: u64 = 7 * 24 * 60 * 60 * 1000;
+const MIN_PLAYERS_COUNT: u8 = 2;
+const MIN_TIMEOUT_BLOCKS: u32 = 5;
+/// A stage can't last longer than a week of 1 second blocks.
+const MAX_TIMEOUT_BLOCKS: u32 = 7 * 24 * 60 * 60;
+/// The lobby is bounded by the gas needed to end a round with all its players.
+const MAX_PLAYERS_COUNT: u8 = 50;
+/// The fee can't be greater than 10% of the pot.
+const MAX_FEE_BPS: u16 = 1_000;
+const BPS_DENOMINATOR: u128 = 10_000;
+
 impl Metadata for ContractMetadata {
     type Init = In<GameConfig>;
-    type Handle = InOut<Action, Event>;
+    type Handle = InOut<Action, Result<Event, RpsError>>;
     type Reply = ();
     type Others = ();
     type Signal = ();
@@ -24,14 +42,14 @@ pub enum Move {
 }
 
 impl Move {
-    pub fn new(number: u8) -> Move {
+    pub fn new(number: u8) -> Result<Move, RpsError> {
         match number {
-            b'0' => Move::Rock,
-            b'1' => Move::Paper,
-            b'2' => Move::Scissors,
-            b'3' => Move::Lizard,
-            b'4' => Move::Spock,
-            _ => panic!("Unknown symbol in move, {number}"),
+            b'0' => Ok(Move::Rock),
+            b'1' => Ok(Move::Paper),
+            b'2' => Ok(Move::Scissors),
+            b'3' => Ok(Move::Lizard),
+            b'4' => Ok(Move::Spock),
+            _ => Err(RpsError::UnknownMove),
         }
     }
 
@@ -73,26 +91,29 @@ pub enum GameStage {
     Preparation,
     InProgress(StageDescription),
     Reveal(StageDescription),
+    /// The players of the finished game can accept the rematch before the open registration.
+    /// `anticipated_players` haven't accepted it yet, `finished_players` have.
+    Rematch(StageDescription),
 }
 
 impl GameStage {
     pub fn game_is_in_progress(&self) -> bool {
         match self {
             GameStage::Preparation => false,
-            GameStage::InProgress(_) | GameStage::Reveal(_) => true,
+            GameStage::InProgress(_) | GameStage::Reveal(_) | GameStage::Rematch(_) => true,
         }
     }
 
     pub fn move_can_be_made(&self) -> bool {
         match self {
             GameStage::Preparation | GameStage::InProgress(_) => true,
-            GameStage::Reveal(_) => false,
+            GameStage::Reveal(_) | GameStage::Rematch(_) => false,
         }
     }
 
     pub fn is_player_in_game(&self, player: &ActorId) -> bool {
         match self {
-            GameStage::Preparation => false,
+            GameStage::Preparation | GameStage::Rematch(_) => false,
             GameStage::InProgress(description) | GameStage::Reveal(description) => {
                 description.anticipated_players.contains(player)
                     || description.finished_players.contains(player)
@@ -102,7 +123,7 @@ impl GameStage {
 
     pub fn current_players(&self) -> Option<BTreeSet<ActorId>> {
         let description = match self {
-            GameStage::Preparation => return None,
+            GameStage::Preparation | GameStage::Rematch(_) => return None,
             GameStage::InProgress(progress_description) => progress_description,
             GameStage::Reveal(reveal_description) => reveal_description,
         };
@@ -116,13 +137,87 @@ impl GameStage {
     }
 }
 
-#[derive(Debug, Encode, Decode, TypeInfo)]
+/// Builds the binary that is hashed with 256-bit blake2b into the move commitment.
+/// The commitment is bound to the player, the game and the round, so it can't be copied by other players
+/// or reused in another round.
+///
+/// # Arguments:
+/// * `real_move`: is the binary move("0" or "1" or "2" or "3" or "4") + "password".
+/// * `player`: is the address of the player who makes the move.
+/// * `game_id`, `round`: are the id and `GameState::round` of the game the move is made in.
+pub fn commitment_preimage(
+    real_move: &[u8],
+    player: &ActorId,
+    game_id: GameId,
+    round: u32,
+) -> Vec<u8> {
+    let mut preimage = real_move.to_vec();
+    preimage.extend_from_slice(player.as_ref());
+    preimage.extend_from_slice(&game_id.to_le_bytes());
+    preimage.extend_from_slice(&round.to_le_bytes());
+
+    preimage
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo, PartialEq, Eq)]
 pub struct Duration {
     pub days: u64,
     pub hours: u64,
     pub minutes: u64,
 }
 
+impl Duration {
+    /// The duration in milliseconds, e.g. for the `*_timeout_ms` of `GameConfig`.
+    /// The result is still checked against the timeout limits by `GameConfig::validate`.
+    ///
+    /// Fails with `ConfigError::DurationOverflow` if it doesn't fit in `u64`.
+    pub fn as_millis(&self) -> Result<u64, ConfigError> {
+        self.days
+            .checked_mul(24)
+            .and_then(|hours| hours.checked_add(self.hours))
+            .and_then(|hours| hours.checked_mul(60))
+            .and_then(|minutes| minutes.checked_add(self.minutes))
+            .and_then(|minutes| minutes.checked_mul(60 * 1000))
+            .ok_or(ConfigError::DurationOverflow)
+    }
+
+    /// The number of blocks of `block_time_ms` that last at least the duration,
+    /// e.g. for the `BlockTimeouts` of `GameConfig`.
+    /// The result is still checked against the timeout limits by `GameConfig::validate`.
+    ///
+    /// Fails with `ConfigError::ZeroBlockTime` if `block_time_ms` is 0
+    /// and with `ConfigError::DurationOverflow` if the number of blocks doesn't fit in `u32`.
+    pub fn as_blocks(&self, block_time_ms: u64) -> Result<u32, ConfigError> {
+        if block_time_ms == 0 {
+            return Err(ConfigError::ZeroBlockTime);
+        }
+
+        let millis = self.as_millis()?;
+        let blocks = millis / block_time_ms + u64::from(millis % block_time_ms != 0);
+
+        u32::try_from(blocks).map_err(|_| ConfigError::DurationOverflow)
+    }
+}
+
+/// The moment the current stage ends at.
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub enum Deadline {
+    /// The timestamp in milliseconds.
+    Timestamp(u64),
+    /// The block height.
+    BlockHeight(u32),
+}
+
+impl Deadline {
+    /// Whether the deadline has passed at the block with this timestamp and height.
+    pub fn has_passed(&self, timestamp: u64, block_height: u32) -> bool {
+        match self {
+            Deadline::Timestamp(deadline) => timestamp > *deadline,
+            Deadline::BlockHeight(deadline) => block_height > *deadline,
+        }
+    }
+}
+
 #[derive(Debug, Encode, Decode, TypeInfo)]
 pub enum RevealResult {
     Continue,
@@ -130,83 +225,679 @@ pub enum RevealResult {
     GameOver { winner: ActorId },
 }
 
+/// The role that allows to administrate the games without being the owner of the program.
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub enum Role {
+    /// Can configure and create the games, manage the series, allowlists and listeners,
+    /// and do everything an operator can.
+    Admin,
+    /// Can stop the games, kick and block the players.
+    Operator,
+}
+
+/// The config of the game changed by `Action::ChangeNextGameConfig`.
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub enum ConfigSlot {
+    /// The config of the current game, changed while its lobby is empty.
+    Current,
+    /// The config that will be applied to the next game.
+    Next,
+}
+
+/// The number of games played back-to-back in the same lobby by the players who have joined the series.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub enum SeriesLength {
+    /// The series ends after this number of games with a winner including the current one.
+    Games(u32),
+    /// The series lasts until the owner ends it.
+    Unlimited,
+}
+
+/// What happened to the game when its current stage has ended without waiting for all the players,
+/// e.g. because the deadline has passed or the last anticipated player has forfeited.
+#[derive(Debug, Encode, Decode, TypeInfo)]
+pub enum StageTransition {
+    /// Not enough players acted in time, so the current stage starts over.
+    StageProlonged,
+    /// The registration is over and the first round has started with these players.
+    GameStarted { players: BTreeSet<ActorId> },
+    /// The moves stage is over and these players must reveal their moves.
+    RevealStarted { players: BTreeSet<ActorId> },
+    /// The round is over without waiting for the rest of the players.
+    RoundEnded(RevealResult),
+    /// Not enough players have accepted the rematch in time,
+    /// so the registration is opened and the accepted players stay in the lobby.
+    RematchDeclined,
+    /// The stage has been prolonged `max_idle_extensions` times of `AbandonPolicy` in a row, so the game is abandoned.
+    /// The bets are returned to `refunded_players` and the new game starts,
+    /// or nothing is returned yet if the game waits for the owner to stop it.
+    GameAbandoned { refunded_players: BTreeSet<ActorId> },
+}
+
+/// Every action replies with `Result<Event, RpsError>`.
+/// On failure it replies `Err(RpsError)` and the value sent with the message is returned.
 #[derive(Debug, Decode, Encode, TypeInfo)]
 pub enum Action {
+    /// Creates a new game with its own lobby, config and pot.
+    ///
+    /// # Arguments:
+    /// * `GameConfig`: is the config of the new game.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner or an admin of the program.
+    /// * The `GameConfig` must meet the same requirements as in `ChangeNextGameConfig`.
+    ///
+    /// On success replies `Ok(Event::GameCreated(GameId))` where `GameId` is the id of the new game.
+    CreateGame(GameConfig),
+
     /// Registers a player for the game.
     /// Player must send value to be registered
     ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
     /// # Requirements:
     /// * Game is not in progress yet. E.g. the `GameStage` must be `GameStage::Preparation`
     /// * `msg::value()` is greater or equal to `bet_size` in the config(refund will return to user).
     /// * Player not registred yet.
     /// * Lobby is not full.
+    /// * The game is not abandoned.
+    /// * `registration_opens_at` of the config has come.
+    /// * The program is not paused.
+    /// * The player is in the `allowlist` of the config if it's set.
+    /// * The player is not blocked.
+    ///
+    /// The game starts at once if `auto_start` is set in the config and the lobby is full after the registration.
     ///
-    /// On success replies `Event::PlayerRegistred`.
-    Register,
+    /// On success replies `Ok(Event::PlayerRegistred)`.
+    Register(GameId),
+
+    /// Removes the player from the lobby and returns the bet.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
+    /// # Requirements:
+    /// * Game is not in progress yet. E.g. the `GameStage` must be `GameStage::Preparation`
+    /// * The `msg::source()` is in the lobby.
+    /// * The game is not abandoned.
+    ///
+    /// On success replies `Ok(Event::PlayerLeft)` with `bet_size` of the config attached.
+    LeaveLobby(GameId),
 
     /// Submits player's move to the program in encrypted form.
     /// Player can't change his move after it.
     ///
     /// # Arguments:
-    /// * `Vec<u8>`: is the binary 256-bit blake2b hash of move("0" or "1" or "2" or "3" or "4") + "password".
+    /// * `GameId`: is the id of the game.
+    /// * `Vec<u8>`: is the binary 256-bit blake2b hash of `commitment_preimage` built from move("0" or "1" or "2" or "3" or "4") + "password", the player's address, the `GameId` and the current `round` of the game.
     ///
     /// # Requirements:
     /// * The `GameStage` must be `GameStage::InProgress(StageDesciption)` where `StageDescription::anticipated_players` must contains `msg::source()`
+    /// * No other player has submitted the same hash in this round.
+    /// * The program is not paused.
+    /// * The game is not abandoned.
     ///
-    /// On success replies `Event::SuccessfulReveal(RevealResult)` where `RevealResult` will correspond to the situation after this reveal.
-    MakeMove(Vec<u8>),
+    /// On success replies `Ok(Event::SuccessfulReveal(RevealResult))` where `RevealResult` will correspond to the situation after this reveal.
+    MakeMove(GameId, Vec<u8>),
 
     /// Reveals the move of the player, with which players must confirm their moves.
     /// In this step the program validates that the hash submitted during the moves stage is equal
     /// to a hashed open string and save this move(first character from string) to determine the winners.
     ///
     /// # Arguments:
+    /// * `GameId`: is the id of the game.
     /// * `Vec<u8>`: is the binary move("0" or "1" or "2" or "3" or "4") + "password" that should be equal to binary that was sent in `MakeMove(Vec<u8>)` without hashing.
     ///
     /// # Requirements:
-    /// * The hashed(by program) `Reveal` binary must be equal to this round `MakeMove` binary.
+    /// * The hashed(by program) `commitment_preimage` of the `Reveal` binary must be equal to this round `MakeMove` binary.
     /// * The `GameStage` must be `GameStage::Reveal(StageDesciption)` where `StageDescription::anticipated_players` must contains `msg::source()`
+    /// * The game is not abandoned.
     ///
-    /// On success replies `Event::SuccessfulMove(ActorId)` where `ActorId` is the moved player's address.
-    Reveal(Vec<u8>),
+    /// On success replies `Ok(Event::SuccessfulMove(ActorId))` where `ActorId` is the moved player's address.
+    Reveal(GameId, Vec<u8>),
 
     /// Changes the game config of the next game.
     /// When the current game ends, this config will be applied.
     ///
     /// # Arguments:
-    /// * `GameConfig`: is the config that will be applied to the next game.
+    /// * `GameId`: is the id of the game.
+    /// * `GameConfig`: is the config that will be applied to the next game, or to the current one
+    ///   if it's on the `Preparation` stage and no one has registered yet.
     ///
     /// # Requirements:
-    /// * The `msg::source()` must be the owner of the program.
-    /// * `players_count_limit` of the `GameConfig` must be greater than 1
-    /// * `entry_timeout` of the `GameConfig` must be greater than 5000(5 sec)
-    /// * `move_timeout` of the `GameConfig` must be greater than 5000(5 sec)
-    /// * `reveal_timeout` of the `GameConfig` must be greater than 5000(5 sec)
+    /// * The `msg::source()` must be the owner or an admin of the program.
+    /// * `players_count_limit` of the `GameConfig` must be greater than 1 and not greater than 50
+    ///   and not less than the number of the players in the lobby
+    /// * `entry_timeout` of the `GameConfig` must be from 5000(5 sec) to 604800000(a week)
+    /// * `move_timeout` of the `GameConfig` must be from 5000(5 sec) to 604800000(a week)
+    /// * `reveal_timeout` of the `GameConfig` must be from 5000(5 sec) to 604800000(a week)
+    /// * `rematch_timeout` of the `GameConfig` must be 0 or from 5000(5 sec) to 604800000(a week)
+    /// * `min_players_to_start` of the `GameConfig` must be greater than 1 and not greater than `players_count_limit`
+    /// * `starts_at` of the `GameConfig` must be later than `registration_opens_at` if both are set
+    /// * `fee_bps` of the `GameConfig` must be less or equal to 1000(10%)
+    /// * `bet_size` of the `GameConfig` must be within the `BetLimits` set by the owner
     ///
-    /// On success replies `Event::GameConfigChanged`.
-    ChangeNextGameConfig(GameConfig),
+    /// On failure replies `Err(RpsError::InvalidConfig(ConfigError))`.
+    /// On success replies `Ok(Event::GameConfigChanged)` with the changed `ConfigSlot`.
+    ChangeNextGameConfig(GameId, GameConfig),
+
+    /// Accepts the rematch with the same bet as `Register`.
+    /// The new game starts at once when `min_players_to_start` players of the finished game have accepted it.
+    /// If fewer have accepted when `rematch_timeout_ms` has passed, they stay registered for the next game
+    /// and the registration opens for everyone.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
+    /// # Requirements:
+    /// * The `GameStage` must be `GameStage::Rematch(StageDesciption)` where `StageDescription::anticipated_players` must contains `msg::source()`
+    /// * `msg::value()` is greater or equal to `bet_size` in the config(refund will return to user).
+    /// * The program is not paused.
+    ///
+    /// On success replies `Ok(Event::RematchAccepted(Option<StageTransition>))`
+    /// where `StageTransition` is `GameStarted` if the new game has started.
+    AcceptRematch(GameId),
+
+    /// Gives up the current game. The bet stays in the pot.
+    /// The player leaves the lobby, so they aren't offered the rematch.
+    /// The stage is resolved at once if the player was the last one it waited for,
+    /// and the game is over if only one player remains.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
+    /// # Requirements:
+    /// * The `GameStage` must be `GameStage::InProgress(StageDesciption)` or `GameStage::Reveal(StageDesciption)` where the `StageDescription` contains `msg::source()`,
+    ///   the players in the `GameStage::Preparation` should use `LeaveLobby` instead.
+    /// * The game is not abandoned.
+    ///
+    /// On success replies `Ok(Event::PlayerForfeited(Option<StageTransition>))` where `StageTransition` describes how the stage has ended
+    /// or `None` if it still waits for other players.
+    Forfeit(GameId),
 
     /// Stops the game.
     /// This action can be used, for example, to change the configuration of the game,
     /// or if the players have gone on strike and do not want to continue playing,
     /// or if the game has gone on for a long time.
-    /// When the admin stops the game, all funds are distributed among the players remaining in the game.
+    /// When the admin stops the game, the pot is distributed among the players remaining in the game.
     /// If the game is in the registration stage, bets will be returned to the entire lobby.
+    /// The players get the value with `Action::Claim`.
+    /// It's the way to resolve the game abandoned with `AbandonPolicy::OwnerResolves`.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner, an admin or an operator of the program.
+    ///
+    /// On success replies `Ok(Event::GameWasStopped(BTreeSet<ActorId>))` where inside are the players who got the money.
+    StopGame(GameId),
+
+    /// Starts, changes or ends the series of games.
+    /// While the series lasts, the players who have joined it are registered for each next game
+    /// with the bets taken from their series balances, and the game starts at once
+    /// if there are at least `min_players_to_start` of them.
+    /// Only the games that end with a winner are counted, after a stopped or abandoned game
+    /// the registration is open as usual.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    /// * `Option<SeriesLength>`: is the length of the series or `None` to end it after the current game.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner or an admin of the program.
+    ///
+    /// On success replies `Ok(Event::SeriesSet)`.
+    SetSeries(GameId, Option<SeriesLength>),
+
+    /// Joins the series to be registered for the next games automatically
+    /// and adds `msg::value()` to the series balance of the player the bets are taken from.
+    /// It doesn't register the player for the current game.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
+    /// # Requirements:
+    /// * The series is set with `SetSeries`.
+    /// * `msg::value()` is not zero.
+    /// * The player is not blocked.
+    ///
+    /// On success replies `Ok(Event::JoinedSeries(u128))` with the series balance of the player.
+    JoinSeries(GameId),
+
+    /// Leaves the series and returns the rest of the series balance.
+    /// The player remains registered for the current game.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
+    /// # Requirements:
+    /// * The player has joined the series.
+    ///
+    /// On success replies `Ok(Event::LeftSeries(u128))` with the rest of the balance attached.
+    LeaveSeries(GameId),
+
+    /// Invites the players to the private lobby, makes the lobby private if it's public.
+    /// The registered players who are not invited lose their places in the lobby,
+    /// their bets are credited to `GameState::pending_withdrawals`.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    /// * `Vec<ActorId>`: is the players to add to the `allowlist` of the current config.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner or an admin of the program.
+    /// * Game is not in progress yet. E.g. the `GameStage` must be `GameStage::Preparation`
+    ///
+    /// On success replies `Ok(Event::AllowlistUpdated)`.
+    AddInvitees(GameId, Vec<ActorId>),
+
+    /// Removes the players from the `allowlist` of the current config.
+    /// The removed players that have registered are removed from the lobby and their bets are credited back.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    /// * `Vec<ActorId>`: is the players to remove from the `allowlist`.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner or an admin of the program.
+    /// * Game is not in progress yet. E.g. the `GameStage` must be `GameStage::Preparation`
+    /// * The lobby is private.
+    ///
+    /// On success replies `Ok(Event::AllowlistUpdated)`.
+    RemoveInvitees(GameId, Vec<ActorId>),
+
+    /// Removes the player from the lobby and credits the bet back to be claimed with `Claim`.
+    /// The player is notified with `Event::PlayerKicked(ActorId)`.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    /// * `ActorId`: is the player to remove.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner, an admin or an operator of the program.
+    /// * Game is not in progress yet. E.g. the `GameStage` must be `GameStage::Preparation`
+    /// * The player is in the lobby.
+    /// * The game is not abandoned.
+    ///
+    /// On success replies `Ok(Event::PlayerKicked(ActorId))`.
+    KickPlayer(GameId, ActorId),
+
+    /// Blocks the player from registering and accepting rematches in all the games.
+    /// It doesn't affect the games the player is already registered in.
+    /// The player leaves all the series, the series balances are credited to `pending_withdrawals` of the games.
+    /// The expired blocks are removed from the blocklist.
+    ///
+    /// # Arguments:
+    /// * `ActorId`: is the player to block.
+    /// * `Option<u64>`: is the timestamp in milliseconds when the block expires or `None` to block the player until `UnblockPlayer`.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner, an admin or an operator of the program.
+    ///
+    /// On success replies `Ok(Event::PlayerBlocked)`.
+    BlockPlayer(ActorId, Option<u64>),
+
+    /// Removes the player from the blocklist.
+    ///
+    /// # Arguments:
+    /// * `ActorId`: is the player to unblock.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner, an admin or an operator of the program.
+    /// * The player is in the blocklist.
+    ///
+    /// On success replies `Ok(Event::PlayerUnblocked)`.
+    UnblockPlayer(ActorId),
+
+    /// Sets the program that receives `GameOverNotification` every time the game ends,
+    /// e.g. the factory that has deployed this program.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    /// * `Option<ActorId>`: is the listener or `None` to stop the notifications.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner or an admin of the program.
+    ///
+    /// On success replies `Ok(Event::GameOverListenerSet)`.
+    SetGameOverListener(GameId, Option<ActorId>),
+
+    /// Sends the program balance that doesn't belong to the pot of any game to the owner,
+    /// e.g. the value sent by mistake with actions that don't require it.
     ///
     /// # Requirements:
     /// * The `msg::source()` must be the owner of the program.
+    /// * The surplus is not zero.
+    ///
+    /// On success replies `Ok(Event::SurplusWithdrawn(u128))` with the withdrawn value attached.
+    WithdrawSurplus,
+
+    /// Proposes the new owner of the program who becomes the owner after `AcceptOwnership`.
+    /// The new proposal replaces the previous one.
+    ///
+    /// # Arguments:
+    /// * `ActorId`: is the proposed owner.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the program.
+    ///
+    /// On success replies `Ok(Event::OwnerProposed(ActorId))`.
+    ProposeOwner(ActorId),
+
+    /// Makes the `msg::source()` the owner of the program.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be proposed with `ProposeOwner`.
+    ///
+    /// On success replies `Ok(Event::OwnershipTransferred(ActorId))` where `ActorId` is the new owner.
+    AcceptOwnership,
+
+    /// Leaves the program without the owner for good, so the owner actions can't be sent anymore.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the program.
+    ///
+    /// On success replies `Ok(Event::OwnershipRenounced)`.
+    RenounceOwnership,
+
+    /// Pauses the program, so the players can't register and make moves in all the games.
+    /// The reveals, refunds and claims can still be completed.
+    /// The stage timeouts aren't applied and the players who have joined a series aren't registered
+    /// until the program is unpaused.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the program.
+    /// * The program is not paused.
+    ///
+    /// On success replies `Ok(Event::Paused)`.
+    Pause,
+
+    /// Unpauses the program.
+    /// The stage deadlines are moved forward by the time the program has been paused,
+    /// so the players have as much time left as they had before the pause.
     ///
-    /// On success replies `Event::GameWasStopped(BTreeSet<ActorId>)` where inside are the players who got the money.
-    StopGame,
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the program.
+    /// * The program is paused.
+    ///
+    /// On success replies `Ok(Event::Unpaused)`.
+    Unpause,
+
+    /// Sets the bounds of `bet_size` for the configs of the games.
+    /// The configs already in use are not affected.
+    ///
+    /// # Arguments:
+    /// * `BetLimits`: is the new bounds of `bet_size`.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the program.
+    /// * `min` of the `BetLimits` must be less or equal to its `max`.
+    ///
+    /// On success replies `Ok(Event::BetLimitsSet)`.
+    SetBetLimits(BetLimits),
+
+    /// Grants the role to the account, replacing its previous role.
+    ///
+    /// # Arguments:
+    /// * `ActorId`: is the account.
+    /// * `Role`: is the role to grant.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the program.
+    ///
+    /// On success replies `Ok(Event::RoleGranted(ActorId, Role))`.
+    GrantRole(ActorId, Role),
+
+    /// Revokes the role of the account.
+    ///
+    /// # Arguments:
+    /// * `ActorId`: is the account.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the program.
+    /// * The account has a role.
+    ///
+    /// On success replies `Ok(Event::RoleRevoked(ActorId))`.
+    RevokeRole(ActorId),
+
+    /// Pays out the winnings and refunds of the player credited in the game.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` has a non-zero balance in `GameState::pending_withdrawals`.
+    ///
+    /// On success replies `Ok(Event::Claimed(u128))` with the claimed value attached.
+    Claim(GameId),
+
+    /// Sends the fees collected in the game to the treasuries of the configs they were kept under,
+    /// so a treasury changed with `ChangeNextGameConfig` gets only the fees of the following games.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the owner of the program.
+    /// * The collected fees are not zero.
+    /// * The fees can be sent to the treasuries, the fees of the treasuries sent before a failed sending are withdrawn.
+    ///
+    /// On success replies `Ok(Event::FeesWithdrawn(u128))` with the total value sent to the treasuries.
+    WithdrawFees(GameId),
+
+    /// Applies the stage transition if the deadline of the current stage has passed.
+    /// Any action does it before being processed, but this one can be sent by anyone
+    /// to move the game on when none of the players does anything.
+    ///
+    /// # Arguments:
+    /// * `GameId`: is the id of the game.
+    ///
+    /// # Requirements:
+    /// * The program is not paused.
+    /// * The current stage has been lasting longer than its timeout in the config.
+    ///
+    /// On success replies `Ok(Event::StageAdvanced(StageTransition))` where `StageTransition` describes what happened.
+    AdvanceStage(GameId),
+
+    /// Internal action that the program sends to itself with a delay to apply the stage timeout
+    /// when nobody interacts with the game.
+    /// It's ignored if the stage has been changed since it was sent.
+    ///
+    /// # Arguments:
+    /// * `game_id`: is the id of the game.
+    /// * `stage_nonce`: is the `GameState::stage_nonce` of the stage whose timeout should be checked.
+    ///
+    /// # Requirements:
+    /// * The `msg::source()` must be the program itself.
+    ///
+    /// Doesn't reply on success.
+    CheckTimeout { game_id: GameId, stage_nonce: u64 },
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
 pub enum Event {
+    GameCreated(GameId),
     PlayerRegistered,
+    PlayerLeft,
+    PlayerKicked(ActorId),
     SuccessfulMove(ActorId),
     SuccessfulReveal(RevealResult),
-    GameConfigChanged,
+    GameConfigChanged(ConfigSlot),
     GameStopped(BTreeSet<ActorId>),
+    PlayerForfeited(Option<StageTransition>),
+    RematchAccepted(Option<StageTransition>),
+    StageAdvanced(StageTransition),
+    GameOverListenerSet,
+    SurplusWithdrawn(u128),
+    OwnerProposed(ActorId),
+    OwnershipTransferred(ActorId),
+    OwnershipRenounced,
+    RoleGranted(ActorId, Role),
+    RoleRevoked(ActorId),
+    Paused,
+    Unpaused,
+    BetLimitsSet,
+    Claimed(u128),
+    FeesWithdrawn(u128),
+    SeriesSet,
+    AllowlistUpdated,
+    PlayerBlocked,
+    PlayerUnblocked,
+    JoinedSeries(u128),
+    LeftSeries(u128),
+}
+
+/// The message sent to the listener set by `Action::SetGameOverListener` when the game ends.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct GameOverNotification {
+    pub game_id: GameId,
+    /// `None` if the game has been stopped or abandoned.
+    pub winner: Option<ActorId>,
+}
+
+/// Errors the program replies with instead of `Event` when an action fails.
+/// The value attached to the failed action is returned.
+/// A stage timeout applied before the action has failed is kept, see `Action::AdvanceStage`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum RpsError {
+    /// The action is allowed only for the owner of the program.
+    NotOwner,
+    /// The `msg::source()` is not proposed as the owner with `ProposeOwner`.
+    NotPendingOwner,
+    /// The action is allowed only for the owner and the admins.
+    NotAdmin,
+    /// The action is allowed only for the owner, the admins and the operators.
+    NotOperator,
+    /// The account has no role to revoke.
+    NoRole,
+    /// The action is not allowed while the program is paused.
+    Paused,
+    /// The program is not paused.
+    NotPaused,
+    /// The game config is invalid.
+    InvalidConfig(ConfigError),
+    /// There is no game with such `GameId`.
+    GameNotFound,
+    /// The action is allowed only before the game starts.
+    GameIsInProgress,
+    /// `msg::value()` is less than `bet_size` of the current config.
+    NotEnoughValue,
+    /// The player is already in the lobby.
+    PlayerAlreadyRegistered,
+    /// The lobby has reached `players_count_limit`.
+    LobbyIsFull,
+    /// The lobby is private and the player is not in its `allowlist`.
+    NotInvited,
+    /// The lobby has no `allowlist`.
+    LobbyIsPublic,
+    /// The player is blocked by the owner.
+    PlayerBlocked,
+    /// The player is not in the blocklist.
+    PlayerNotBlocked,
+    /// The game is not in the `GameStage::InProgress` stage.
+    NotMoveStage,
+    /// The player is not expected to act in the current stage.
+    PlayerNotInGame,
+    /// The move hash is not a 256-bit hash.
+    WrongMoveHashFormat,
+    /// The game is not in the `GameStage::Reveal` stage.
+    NotRevealStage,
+    /// The game is not in the `GameStage::Rematch` stage.
+    NotRematchStage,
+    /// The player has already revealed the move in this round.
+    PlayerAlreadyRevealed,
+    /// There is no saved move of this player to compare the reveal with.
+    MoveNotFound,
+    /// The revealed move doesn't match the hash submitted with `MakeMove`.
+    MoveMismatch,
+    /// Another player has already submitted the same move hash in this round.
+    DuplicateCommitment,
+    /// The timeout of the current stage hasn't passed yet.
+    DeadlineNotReached,
+    /// The action can be sent only by the program itself.
+    InternalAction,
+    /// The revealed move doesn't start with "0", "1", "2", "3" or "4".
+    UnknownMove,
+    /// `registration_opens_at` of the config hasn't come yet.
+    RegistrationNotOpen,
+    /// The game is abandoned and waits for the owner to stop it.
+    GameAbandoned,
+    /// The whole program balance belongs to the pots and pending withdrawals of the games.
+    NoSurplus,
+    /// The player has nothing to claim in this game.
+    NothingToClaim,
+    /// No fees have been collected in this game since the last withdrawal.
+    NoFees,
+    /// The game is not played in series.
+    SeriesNotActive,
+    /// The player hasn't joined the series.
+    NotInSeries,
+    /// The action requires a non-zero `msg::value()`.
+    ZeroValue,
+    /// The fees couldn't be sent to a treasury, they stay in the game to be withdrawn later.
+    FeesNotSent,
+}
+
+/// Why the game config was rejected.
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `players_count_limit` is less than 2.
+    PlayersCountLimitTooLow,
+    /// `players_count_limit` is greater than 50.
+    PlayersCountLimitTooHigh,
+    /// `players_count_limit` is less than the number of the players already in the lobby.
+    PlayersCountLimitBelowLobby,
+    /// `min_players_to_start` is less than 2.
+    MinPlayersToStartTooLow,
+    /// `min_players_to_start` is greater than `players_count_limit`.
+    MinPlayersToStartAboveLimit,
+    /// `entry_timeout_ms` is less than 5000.
+    EntryTimeoutTooLow,
+    /// `entry_timeout_ms` is greater than a week.
+    EntryTimeoutTooHigh,
+    /// `move_timeout_ms` is less than 5000.
+    MoveTimeoutTooLow,
+    /// `move_timeout_ms` is greater than a week.
+    MoveTimeoutTooHigh,
+    /// `reveal_timeout_ms` is less than 5000.
+    RevealTimeoutTooLow,
+    /// `reveal_timeout_ms` is greater than a week.
+    RevealTimeoutTooHigh,
+    /// `rematch_timeout_ms` is not 0 and less than 5000.
+    RematchTimeoutTooLow,
+    /// `rematch_timeout_ms` is greater than a week.
+    RematchTimeoutTooHigh,
+    /// A timeout of the `BlockTimeouts` is less than 5 blocks, except for the disabled rematch.
+    BlockTimeoutTooLow,
+    /// A timeout of the `BlockTimeouts` is greater than 604800 blocks.
+    BlockTimeoutTooHigh,
+    /// `starts_at` is not later than `registration_opens_at`.
+    StartBeforeRegistrationOpens,
+    /// `fee_bps` is greater than 1000(10%).
+    FeeTooHigh,
+    /// `bet_size` is less than `min` of the `BetLimits`.
+    BetTooLow,
+    /// `bet_size` is greater than `max` of the `BetLimits`.
+    BetTooHigh,
+    /// `min` of the `BetLimits` is greater than its `max`.
+    InvalidBetLimits,
+    /// The `Duration` doesn't fit in `u64` milliseconds or `u32` blocks.
+    DurationOverflow,
+    /// `block_time_ms` or the block time the `Duration` is converted to blocks with is 0.
+    ZeroBlockTime,
+    /// A `*_timeout_ms` lasts more than 604800 blocks of `block_time_ms`,
+    /// longer than the gas reservation for its timeout check lives.
+    TimeoutTooManyBlocks,
+    /// `fee_bps` is not 0 while `treasury` is the zero address.
+    ZeroTreasury,
+}
+
+impl From<ConfigError> for RpsError {
+    fn from(error: ConfigError) -> Self {
+        RpsError::InvalidConfig(error)
+    }
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -225,18 +916,329 @@ pub enum StateReply {
     CurrentStageTimestamp(u64),
 }
 
+/// What happens to the game when nobody acts in time and the stage is prolonged again and again.
+#[derive(Debug, Default, Clone, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub enum AbandonPolicy {
+    /// The stage is prolonged until somebody acts.
+    #[default]
+    Prolong,
+    /// After `max_idle_extensions` prolongations in a row the game is abandoned and the bets are returned:
+    /// to the whole lobby during the registration or equally to the remaining players of the started game.
+    Refund { max_idle_extensions: u32 },
+    /// After `max_idle_extensions` prolongations in a row the game is abandoned and frozen
+    /// until the owner stops it with `Action::StopGame`.
+    OwnerResolves { max_idle_extensions: u32 },
+}
+
+/// The bounds of `bet_size` in the configs of the games, set by the owner with `Action::SetBetLimits`.
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct BetLimits {
+    pub min: u128,
+    pub max: u128,
+}
+
+impl Default for BetLimits {
+    fn default() -> Self {
+        Self {
+            min: 0,
+            max: u128::MAX,
+        }
+    }
+}
+
+impl BetLimits {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.min > self.max {
+            return Err(ConfigError::InvalidBetLimits);
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_bet(&self, bet_size: u128) -> Result<(), ConfigError> {
+        if bet_size < self.min {
+            return Err(ConfigError::BetTooLow);
+        }
+
+        if bet_size > self.max {
+            return Err(ConfigError::BetTooHigh);
+        }
+
+        Ok(())
+    }
+}
+
+/// The stage timeouts in blocks, see `GameConfig::block_timeouts`.
+#[derive(Debug, Default, Clone, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct BlockTimeouts {
+    pub entry_timeout_blocks: u32,
+    pub move_timeout_blocks: u32,
+    pub reveal_timeout_blocks: u32,
+    /// 0 disables the rematch.
+    pub rematch_timeout_blocks: u32,
+}
+
+impl BlockTimeouts {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let timeouts = [
+            self.entry_timeout_blocks,
+            self.move_timeout_blocks,
+            self.reveal_timeout_blocks,
+        ];
+        let rematch_timeout =
+            (self.rematch_timeout_blocks != 0).then_some(self.rematch_timeout_blocks);
+
+        for timeout in timeouts.into_iter().chain(rematch_timeout) {
+            if timeout < MIN_TIMEOUT_BLOCKS {
+                return Err(ConfigError::BlockTimeoutTooLow);
+            }
+
+            if timeout > MAX_TIMEOUT_BLOCKS {
+                return Err(ConfigError::BlockTimeoutTooHigh);
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default, Clone, Encode, Decode, TypeInfo, PartialEq)]
 pub struct GameConfig {
     pub bet_size: u128,
     pub players_count_limit: u8,
+    /// The only players who can register if it's set, otherwise the lobby is public.
+    pub allowlist: Option<BTreeSet<ActorId>>,
+    /// The game starts when the registration is over only if at least this number of players is in the lobby,
+    /// otherwise the registration is prolonged.
+    pub min_players_to_start: u8,
+    /// Start the game right after the lobby reaches `players_count_limit` without waiting for `entry_timeout_ms`.
+    pub auto_start: bool,
     pub entry_timeout_ms: u64,
     pub move_timeout_ms: u64,
     pub reveal_timeout_ms: u64,
+    /// How long the players of the game ended with a winner can accept the rematch, 0 disables the rematch.
+    pub rematch_timeout_ms: u64,
+    /// The stage timeouts in blocks used instead of the `*_timeout_ms` ones if they are set,
+    /// so the deadlines don't depend on the block time.
+    pub block_timeouts: Option<BlockTimeouts>,
+    /// The expected block time of the network in milliseconds.
+    /// The timeout checks of the `*_timeout_ms` and `starts_at` deadlines are scheduled in blocks of this duration.
+    pub block_time_ms: u64,
+    /// The timestamp in milliseconds before which players can't register.
+    pub registration_opens_at: Option<u64>,
+    /// The timestamp in milliseconds at which the registration ends instead of `entry_timeout_ms` after the stage start.
+    /// If there are not enough players by then, the registration is prolonged by `entry_timeout_ms` as usual.
+    pub starts_at: Option<u64>,
+    pub abandon_policy: AbandonPolicy,
+    /// The part of the pot in basis points(1/100 of a percent) that is kept by the house when the winner is paid out.
+    /// Refunds are made without the fee.
+    pub fee_bps: u16,
+    /// The account that receives the fees withdrawn with `Action::WithdrawFees`.
+    pub treasury: ActorId,
+}
+
+fn validate_timeout(
+    timeout_ms: u64,
+    too_low: ConfigError,
+    too_high: ConfigError,
+) -> Result<(), ConfigError> {
+    if timeout_ms < MIN_TIMEOUT_MS {
+        return Err(too_low);
+    }
+
+    if timeout_ms > MAX_TIMEOUT_MS {
+        return Err(too_high);
+    }
+
+    Ok(())
+}
+
+impl GameConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.players_count_limit < MIN_PLAYERS_COUNT {
+            return Err(ConfigError::PlayersCountLimitTooLow);
+        }
+
+        if self.players_count_limit > MAX_PLAYERS_COUNT {
+            return Err(ConfigError::PlayersCountLimitTooHigh);
+        }
+
+        if self.min_players_to_start < MIN_PLAYERS_COUNT {
+            return Err(ConfigError::MinPlayersToStartTooLow);
+        }
+
+        if self.min_players_to_start > self.players_count_limit {
+            return Err(ConfigError::MinPlayersToStartAboveLimit);
+        }
+
+        if self.block_time_ms == 0 {
+            return Err(ConfigError::ZeroBlockTime);
+        }
+
+        if let Some(block_timeouts) = &self.block_timeouts {
+            block_timeouts.validate()?;
+        } else {
+            validate_timeout(
+                self.entry_timeout_ms,
+                ConfigError::EntryTimeoutTooLow,
+                ConfigError::EntryTimeoutTooHigh,
+            )?;
+            validate_timeout(
+                self.move_timeout_ms,
+                ConfigError::MoveTimeoutTooLow,
+                ConfigError::MoveTimeoutTooHigh,
+            )?;
+            validate_timeout(
+                self.reveal_timeout_ms,
+                ConfigError::RevealTimeoutTooLow,
+                ConfigError::RevealTimeoutTooHigh,
+            )?;
+            if self.rematch_timeout_ms != 0 {
+                validate_timeout(
+                    self.rematch_timeout_ms,
+                    ConfigError::RematchTimeoutTooLow,
+                    ConfigError::RematchTimeoutTooHigh,
+                )?;
+            }
+            if self.max_timeout_blocks() > MAX_TIMEOUT_BLOCKS {
+                return Err(ConfigError::TimeoutTooManyBlocks);
+            }
+        }
+
+        if let (Some(registration_opens_at), Some(starts_at)) =
+            (self.registration_opens_at, self.starts_at)
+        {
+            if starts_at <= registration_opens_at {
+                return Err(ConfigError::StartBeforeRegistrationOpens);
+            }
+        }
+
+        if self.fee_bps > MAX_FEE_BPS {
+            return Err(ConfigError::FeeTooHigh);
+        }
+
+        if self.fee_bps != 0 && self.treasury.is_zero() {
+            return Err(ConfigError::ZeroTreasury);
+        }
+
+        Ok(())
+    }
+
+    /// The longest stage timeout in blocks, the `*_timeout_ms` ones are rounded up to whole blocks of `block_time_ms`.
+    pub fn max_timeout_blocks(&self) -> u32 {
+        match &self.block_timeouts {
+            Some(block_timeouts) => block_timeouts
+                .entry_timeout_blocks
+                .max(block_timeouts.move_timeout_blocks)
+                .max(block_timeouts.reveal_timeout_blocks)
+                .max(block_timeouts.rematch_timeout_blocks),
+            None => {
+                let timeout_ms = self
+                    .entry_timeout_ms
+                    .max(self.move_timeout_ms)
+                    .max(self.reveal_timeout_ms)
+                    .max(self.rematch_timeout_ms);
+                let block_time_ms = self.block_time_ms.max(1);
+                let blocks = timeout_ms.saturating_add(block_time_ms - 1) / block_time_ms;
+
+                u32::try_from(blocks).unwrap_or(u32::MAX)
+            }
+        }
+    }
+
+    /// Whether the player can register with this config.
+    pub fn is_invited(&self, player: &ActorId) -> bool {
+        match &self.allowlist {
+            Some(allowlist) => allowlist.contains(player),
+            None => true,
+        }
+    }
+
+    /// The deadline of the `stage` that has started at the block with `stage_start_timestamp` and `stage_start_block`.
+    /// `starts_at` is a timestamp even if the `block_timeouts` are set.
+    pub fn stage_deadline(
+        &self,
+        stage: &GameStage,
+        stage_start_timestamp: u64,
+        stage_start_block: u32,
+    ) -> Deadline {
+        if let (GameStage::Preparation, Some(starts_at)) = (stage, self.starts_at) {
+            if starts_at > stage_start_timestamp {
+                return Deadline::Timestamp(starts_at);
+            }
+        }
+
+        match &self.block_timeouts {
+            Some(block_timeouts) => {
+                let timeout = match stage {
+                    GameStage::Preparation => block_timeouts.entry_timeout_blocks,
+                    GameStage::InProgress(_) => block_timeouts.move_timeout_blocks,
+                    GameStage::Reveal(_) => block_timeouts.reveal_timeout_blocks,
+                    GameStage::Rematch(_) => block_timeouts.rematch_timeout_blocks,
+                };
+
+                Deadline::BlockHeight(stage_start_block + timeout)
+            }
+            None => {
+                let timeout = match stage {
+                    GameStage::Preparation => self.entry_timeout_ms,
+                    GameStage::InProgress(_) => self.move_timeout_ms,
+                    GameStage::Reveal(_) => self.reveal_timeout_ms,
+                    GameStage::Rematch(_) => self.rematch_timeout_ms,
+                };
+
+                Deadline::Timestamp(stage_start_timestamp + timeout)
+            }
+        }
+    }
+
+    /// Whether the players of the game ended with a winner are offered the rematch.
+    pub fn rematch_is_enabled(&self) -> bool {
+        match &self.block_timeouts {
+            Some(block_timeouts) => block_timeouts.rematch_timeout_blocks != 0,
+            None => self.rematch_timeout_ms != 0,
+        }
+    }
+
+    /// The fee kept from the `pot` paid out to the winner.
+    pub fn fee(&self, pot: u128) -> u128 {
+        pot * self.fee_bps as u128 / BPS_DENOMINATOR
+    }
 }
 
 #[derive(Debug, Default, Encode, Decode, TypeInfo)]
 pub struct ContractState {
     pub owner: ActorId,
+    /// The owner proposed with `Action::ProposeOwner` who hasn't accepted the ownership yet.
+    pub pending_owner: Option<ActorId>,
+    pub roles: Vec<(ActorId, Role)>,
+    /// Whether the registrations and moves are paused with `Action::Pause`.
+    pub paused: bool,
+    pub bet_limits: BetLimits,
+    /// The players who can't register in any game and the timestamps when their blocks expire, `None` if they don't.
+    pub blocklist: Vec<(ActorId, Option<u64>)>,
+    pub games: Vec<(GameId, GameState)>,
+    pub next_game_id: GameId,
+}
+
+impl ContractState {
+    pub fn game(&self, game_id: GameId) -> Option<&GameState> {
+        self.games
+            .iter()
+            .find(|(id, _)| *id == game_id)
+            .map(|(_, game)| game)
+    }
+
+    /// Whether the player can't register at the `timestamp` because of the blocklist.
+    pub fn is_blocked(&self, player: &ActorId, timestamp: u64) -> bool {
+        self.blocklist.iter().any(|(blocked, expires_at)| {
+            blocked == player && !matches!(expires_at, Some(expires_at) if *expires_at <= timestamp)
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Encode, Decode, TypeInfo)]
+pub struct GameState {
     pub lobby: Vec<ActorId>,
     pub game_config: GameConfig,
     pub stage: GameStage,
@@ -244,4 +1246,23 @@ pub struct ContractState {
     pub player_moves: Vec<(ActorId, Move)>,
     pub next_game_config: Option<GameConfig>,
     pub current_stage_start_timestamp: u64,
+    /// The block height at which the current stage has started, used with the `block_timeouts` of the config.
+    pub current_stage_start_block: u32,
+    /// The number of the current round. It's not reset when the next game starts in the same lobby,
+    /// so move commitments can't be replayed.
+    pub round: u32,
+    pub stage_nonce: u64,
+    pub idle_extensions: u32,
+    pub abandoned: bool,
+    /// The bets of the current game that haven't been paid out yet.
+    pub pot: u128,
+    /// The winnings and refunds that the players can get with `Action::Claim`.
+    pub pending_withdrawals: Vec<(ActorId, u128)>,
+    /// The fees kept from the payouts that haven't been withdrawn yet by the treasuries they belong to.
+    pub fees: Vec<(ActorId, u128)>,
+    /// The rest of the current series or `None` if the games are not played in series.
+    pub series: Option<SeriesLength>,
+    /// The players who have joined the series and their balances the bets are taken from.
+    pub series_balances: Vec<(ActorId, u128)>,
+    pub game_over_listener: Option<ActorId>,
 }
@@ -1,17 +1,329 @@
 use gmeta::Metadata;
-use gstd::{debug, errors::Result as GstdResult, exec, msg, prelude::*, ActorId, MessageId};
+use gstd::{
+    debug, errors::Result as GstdResult, exec, msg, prelude::*, ActorId, MessageId, ReservationId,
+};
 use hashbrown::{HashMap, HashSet};
 use rps_io::*;
 
 mod helper_functions;
 mod validations;
 
-use crate::contract::validations::validate_game_config;
-static mut RPS_GAME: Option<RPSGame> = None;
+static mut CONTRACT: Option<Contract> = None;
 
 #[derive(Debug, Default)]
-pub struct RPSGame {
+pub struct Contract {
     pub owner: ActorId,
+    pub pending_owner: Option<ActorId>,
+    pub roles: HashMap<ActorId, Role>,
+    pub paused: bool,
+    pub paused_at_timestamp: u64,
+    pub paused_at_block: u32,
+    pub bet_limits: BetLimits,
+    pub blocklist: HashMap<ActorId, Option<u64>>,
+    pub games: HashMap<GameId, RPSGame>,
+    pub next_game_id: GameId,
+}
+
+impl Contract {
+    fn process(&mut self, action: Action) -> Result<(), RpsError> {
+        match action {
+            Action::CreateGame(config) => self.create_game(config),
+            Action::Register(game_id) => {
+                self.validate_not_paused()?;
+                self.validate_player_is_not_blocked(&msg::source())?;
+                self.game(game_id)?.register()
+            }
+            Action::LeaveLobby(game_id) => self.game(game_id)?.leave_lobby(),
+            Action::MakeMove(game_id, hashed_move) => {
+                self.validate_not_paused()?;
+                self.game(game_id)?.make_move(hashed_move)
+            }
+            Action::Reveal(game_id, real_move) => self.game(game_id)?.reveal(real_move),
+            Action::ChangeNextGameConfig(game_id, config) => {
+                self.validate_source_is_admin()?;
+                self.validate_game_config(&config)?;
+                self.game(game_id)?.set_next_game_config(config)
+            }
+            Action::AcceptRematch(game_id) => {
+                self.validate_not_paused()?;
+                self.validate_player_is_not_blocked(&msg::source())?;
+                self.game(game_id)?.accept_rematch()
+            }
+            Action::Forfeit(game_id) => self.game(game_id)?.forfeit(),
+            Action::StopGame(game_id) => {
+                self.validate_source_is_operator()?;
+                self.game(game_id)?.stop_the_game()
+            }
+            Action::SetSeries(game_id, series) => {
+                self.validate_source_is_admin()?;
+                self.game(game_id)?.set_series(series)
+            }
+            Action::JoinSeries(game_id) => {
+                self.validate_player_is_not_blocked(&msg::source())?;
+                self.game(game_id)?.join_series()
+            }
+            Action::LeaveSeries(game_id) => self.game(game_id)?.leave_series(),
+            Action::AddInvitees(game_id, invitees) => {
+                self.validate_source_is_admin()?;
+                self.game(game_id)?.add_invitees(invitees)
+            }
+            Action::RemoveInvitees(game_id, invitees) => {
+                self.validate_source_is_admin()?;
+                self.game(game_id)?.remove_invitees(invitees)
+            }
+            Action::KickPlayer(game_id, player) => {
+                self.validate_source_is_operator()?;
+                self.game(game_id)?.kick_player(player)
+            }
+            Action::BlockPlayer(player, expires_at) => self.block_player(player, expires_at),
+            Action::UnblockPlayer(player) => self.unblock_player(player),
+            Action::SetGameOverListener(game_id, listener) => {
+                self.validate_source_is_admin()?;
+                self.game(game_id)?.set_game_over_listener(listener)
+            }
+            Action::AdvanceStage(game_id) => {
+                self.validate_not_paused()?;
+                self.games
+                    .get_mut(&game_id)
+                    .ok_or(RpsError::GameNotFound)?
+                    .advance_stage()
+            }
+            Action::CheckTimeout {
+                game_id,
+                stage_nonce,
+            } => {
+                self.validate_source_is_program()?;
+                self.game(game_id)?.check_timeout(stage_nonce)
+            }
+            Action::WithdrawSurplus => self.withdraw_surplus(),
+            Action::ProposeOwner(owner) => self.propose_owner(owner),
+            Action::AcceptOwnership => self.accept_ownership(),
+            Action::RenounceOwnership => self.renounce_ownership(),
+            Action::Pause => self.set_paused(true),
+            Action::Unpause => self.set_paused(false),
+            Action::SetBetLimits(bet_limits) => self.set_bet_limits(bet_limits),
+            Action::GrantRole(account, role) => self.grant_role(account, role),
+            Action::RevokeRole(account) => self.revoke_role(account),
+            Action::Claim(game_id) => self.game(game_id)?.claim(),
+            Action::WithdrawFees(game_id) => {
+                self.validate_source_is_owner()?;
+                self.game(game_id)?.withdraw_fees()
+            }
+        }
+    }
+
+    /// Finds the game and applies its stage timeout if the deadline has passed.
+    fn game(&mut self, game_id: GameId) -> Result<&mut RPSGame, RpsError> {
+        let game = self.games.get_mut(&game_id).ok_or(RpsError::GameNotFound)?;
+        game.change_stage_by_timeout_if_needed();
+
+        Ok(game)
+    }
+
+    fn create_game(&mut self, config: GameConfig) -> Result<(), RpsError> {
+        self.validate_source_is_admin()?;
+        self.validate_game_config(&config)?;
+
+        let game_id = self.insert_game(config);
+
+        reply_ok(Event::GameCreated(game_id), 0);
+
+        Ok(())
+    }
+
+    fn withdraw_surplus(&mut self) -> Result<(), RpsError> {
+        self.validate_source_is_owner()?;
+
+        let surplus = self.surplus();
+        if surplus == 0 {
+            return Err(RpsError::NoSurplus);
+        }
+
+        reply_ok(Event::SurplusWithdrawn(surplus), surplus);
+
+        Ok(())
+    }
+
+    fn propose_owner(&mut self, owner: ActorId) -> Result<(), RpsError> {
+        self.validate_source_is_owner()?;
+
+        self.pending_owner = Some(owner);
+
+        reply_ok(Event::OwnerProposed(owner), 0);
+
+        Ok(())
+    }
+
+    fn accept_ownership(&mut self) -> Result<(), RpsError> {
+        self.validate_source_is_pending_owner()?;
+
+        self.owner = msg::source();
+        self.pending_owner = None;
+
+        reply_ok(Event::OwnershipTransferred(self.owner), 0);
+
+        Ok(())
+    }
+
+    fn renounce_ownership(&mut self) -> Result<(), RpsError> {
+        self.validate_source_is_owner()?;
+
+        self.owner = ActorId::zero();
+        self.pending_owner = None;
+
+        reply_ok(Event::OwnershipRenounced, 0);
+
+        Ok(())
+    }
+
+    fn set_paused(&mut self, paused: bool) -> Result<(), RpsError> {
+        self.validate_source_is_owner()?;
+
+        if self.paused == paused {
+            return Err(if paused {
+                RpsError::Paused
+            } else {
+                RpsError::NotPaused
+            });
+        }
+
+        self.paused = paused;
+        if paused {
+            self.paused_at_timestamp = exec::block_timestamp();
+            self.paused_at_block = exec::block_height();
+        }
+
+        for game in self.games.values_mut() {
+            if paused {
+                game.paused = true;
+            } else {
+                game.resume(self.paused_at_timestamp, self.paused_at_block);
+            }
+        }
+
+        reply_ok(
+            if paused {
+                Event::Paused
+            } else {
+                Event::Unpaused
+            },
+            0,
+        );
+
+        Ok(())
+    }
+
+    fn set_bet_limits(&mut self, bet_limits: BetLimits) -> Result<(), RpsError> {
+        self.validate_source_is_owner()?;
+        bet_limits.validate()?;
+
+        self.bet_limits = bet_limits;
+
+        reply_ok(Event::BetLimitsSet, 0);
+
+        Ok(())
+    }
+
+    fn block_player(&mut self, player: ActorId, expires_at: Option<u64>) -> Result<(), RpsError> {
+        self.validate_source_is_operator()?;
+
+        self.remove_expired_blocks();
+        self.blocklist.insert(player, expires_at);
+        for game in self.games.values_mut() {
+            game.remove_from_series(&player);
+        }
+
+        reply_ok(Event::PlayerBlocked, 0);
+
+        Ok(())
+    }
+
+    fn unblock_player(&mut self, player: ActorId) -> Result<(), RpsError> {
+        self.validate_source_is_operator()?;
+
+        self.remove_expired_blocks();
+        self.blocklist
+            .remove(&player)
+            .ok_or(RpsError::PlayerNotBlocked)?;
+
+        reply_ok(Event::PlayerUnblocked, 0);
+
+        Ok(())
+    }
+
+    fn grant_role(&mut self, account: ActorId, role: Role) -> Result<(), RpsError> {
+        self.validate_source_is_owner()?;
+
+        self.roles.insert(account, role);
+
+        reply_ok(Event::RoleGranted(account, role), 0);
+
+        Ok(())
+    }
+
+    fn revoke_role(&mut self, account: ActorId) -> Result<(), RpsError> {
+        self.validate_source_is_owner()?;
+
+        self.roles.remove(&account).ok_or(RpsError::NoRole)?;
+
+        reply_ok(Event::RoleRevoked(account), 0);
+
+        Ok(())
+    }
+
+    /// The program balance that doesn't belong to the pot, `pending_withdrawals`, fees or series balances of any game,
+    /// e.g. the value sent with actions that don't require it.
+    fn surplus(&self) -> u128 {
+        let tracked: u128 = self
+            .games
+            .values()
+            .map(|game| {
+                game.pot
+                    + game.fees.values().sum::<u128>()
+                    + game.pending_withdrawals.values().sum::<u128>()
+                    + game.series_balances.values().sum::<u128>()
+            })
+            .sum();
+
+        exec::value_available().saturating_sub(tracked)
+    }
+
+    /// Whether the player is in the blocklist and the block hasn't expired yet.
+    fn is_blocked(&self, player: &ActorId) -> bool {
+        match self.blocklist.get(player) {
+            None => false,
+            Some(None) => true,
+            Some(Some(expires_at)) => *expires_at > exec::block_timestamp(),
+        }
+    }
+
+    fn remove_expired_blocks(&mut self) {
+        let now = exec::block_timestamp();
+        self.blocklist
+            .retain(|_, expires_at| !matches!(expires_at, Some(expires_at) if *expires_at <= now));
+    }
+
+    fn insert_game(&mut self, config: GameConfig) -> GameId {
+        let game_id = self.next_game_id;
+        let game = RPSGame {
+            game_id,
+            game_config: config,
+            current_stage_start_timestamp: exec::block_timestamp(),
+            current_stage_start_block: exec::block_height(),
+            paused: self.paused,
+            ..Default::default()
+        };
+
+        self.games.insert(game_id, game);
+        self.next_game_id += 1;
+
+        game_id
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RPSGame {
+    pub game_id: GameId,
     pub lobby: HashSet<ActorId>,
     pub game_config: GameConfig,
     pub stage: GameStage,
@@ -19,76 +331,304 @@ pub struct RPSGame {
     pub player_moves: HashMap<ActorId, Move>,
     pub next_game_config: Option<GameConfig>,
     pub current_stage_start_timestamp: u64,
+    pub current_stage_start_block: u32,
+    pub round: u32,
+    pub stage_nonce: u64,
+    pub scheduled_check_nonce: Option<u64>,
+    pub idle_extensions: u32,
+    pub abandoned: bool,
+    pub pot: u128,
+    pub pending_withdrawals: HashMap<ActorId, u128>,
+    pub fees: HashMap<ActorId, u128>,
+    pub series: Option<SeriesLength>,
+    pub series_balances: HashMap<ActorId, u128>,
+    pub series_registration_pending: bool,
+    pub game_over_listener: Option<ActorId>,
+    /// The gas reservations for the timeout checks and the block heights they expire at.
+    pub reservations: Vec<(ReservationId, u32)>,
+    pub paused: bool,
 }
 
 impl RPSGame {
-    fn register(&mut self) {
-        self.validate_game_is_not_in_progress();
-        self.validate_bet(msg::value());
-        self.validate_there_is_no_such_player(&msg::source());
-        self.validate_there_is_place_for_player();
+    fn register(&mut self) -> Result<(), RpsError> {
+        self.validate_game_is_not_abandoned()?;
+        self.validate_game_is_not_in_progress()?;
+        self.validate_registration_is_open()?;
+        self.validate_bet(msg::value())?;
+        self.validate_there_is_no_such_player(&msg::source())?;
+        self.validate_player_is_invited(&msg::source())?;
+        self.validate_there_is_place_for_player()?;
 
         let change = msg::value() - self.game_config.bet_size;
         self.lobby.insert(msg::source());
+        self.pot += self.game_config.bet_size;
+        self.reserve_gas();
+        self.start_game_if_lobby_is_full();
+        self.schedule_timeout_check();
+
+        reply_ok(Event::PlayerRegistered, change);
 
-        msg::reply(Event::PlayerRegistered, change).expect("Can't send reply");
+        Ok(())
     }
 
-    fn make_move(&mut self, move_hash: Vec<u8>) {
+    fn leave_lobby(&mut self) -> Result<(), RpsError> {
+        self.validate_game_is_not_abandoned()?;
+        self.validate_game_is_not_in_progress()?;
+        self.validate_player_is_in_lobby(&msg::source())?;
+
+        let bet = self.game_config.bet_size;
+        self.lobby.remove(&msg::source());
+        self.pot -= bet;
+
+        reply_ok(Event::PlayerLeft, bet);
+
+        Ok(())
+    }
+
+    fn make_move(&mut self, move_hash: Vec<u8>) -> Result<(), RpsError> {
         let player_id = &msg::source();
-        self.validate_player_can_make_a_move(player_id);
+        self.validate_game_is_not_abandoned()?;
+        self.validate_player_can_make_a_move(player_id)?;
+
+        let move_hash = move_hash
+            .try_into()
+            .map_err(|_| RpsError::WrongMoveHashFormat)?;
+        self.validate_commitment_is_unique(&move_hash)?;
 
-        self.save_move(&msg::source(), move_hash);
+        self.reserve_gas();
+        self.save_move(player_id, move_hash);
         self.try_to_transit_to_reveal_stage_after_move();
+        self.schedule_timeout_check();
 
-        msg::reply(Event::SuccessfulMove(*player_id), 0).expect("Reply error");
+        reply_ok(Event::SuccessfulMove(*player_id), 0);
+
+        Ok(())
     }
 
-    fn reveal(&mut self, real_move: Vec<u8>) {
+    fn reveal(&mut self, real_move: Vec<u8>) -> Result<(), RpsError> {
         let player = &msg::source();
 
-        self.validate_player_can_reveal(player);
-        self.validate_reveal(player, real_move.as_slice());
+        self.validate_game_is_not_abandoned()?;
+        self.validate_player_can_reveal(player)?;
+        self.validate_reveal(player, real_move.as_slice())?;
+
+        let users_move = real_move.first().ok_or(RpsError::UnknownMove)?;
+        let users_move = Move::new(*users_move)?;
 
-        self.save_real_move(player, real_move);
+        self.reserve_gas();
+        self.save_real_move(player, users_move);
         let result = self.end_round_if_needed();
+        self.schedule_timeout_check();
+
+        reply_ok(Event::SuccessfulReveal(result), 0);
 
-        msg::reply(Event::SuccessfulReveal(result), 0).expect("Reply error");
+        Ok(())
     }
 
-    fn set_next_game_config(&mut self, config: GameConfig) {
-        validate_game_config(&config);
-        self.validate_source_is_owner();
+    fn set_next_game_config(&mut self, config: GameConfig) -> Result<(), RpsError> {
+        self.validate_config_fits_lobby(&config)?;
 
-        self.next_game_config = Some(config);
+        let slot = if matches!(self.stage, GameStage::Preparation) && self.lobby.is_empty() {
+            self.next_game_config = None;
+            self.game_config = config;
 
-        msg::reply(Event::GameConfigChanged, 0).expect("Reply error");
+            ConfigSlot::Current
+        } else {
+            self.next_game_config = Some(config);
+
+            ConfigSlot::Next
+        };
+
+        reply_ok(Event::GameConfigChanged(slot), 0);
+
+        Ok(())
     }
 
-    fn stop_the_game(&mut self) {
-        self.validate_source_is_owner();
+    fn accept_rematch(&mut self) -> Result<(), RpsError> {
+        let player = &msg::source();
 
-        let players = if matches!(self.stage, GameStage::Preparation) {
-            self.lobby.iter().for_each(|player| {
-                msg::send(*player, "STOP", self.game_config.bet_size).expect("Can't send reward");
-            });
+        self.validate_player_can_accept_rematch(player)?;
+        self.validate_player_is_invited(player)?;
+        self.validate_bet(msg::value())?;
 
-            self.lobby.iter().copied().collect()
-        } else {
-            let players = self.stage.current_players().expect("Game is not started");
+        let change = msg::value() - self.game_config.bet_size;
+        self.lobby.insert(*player);
+        self.pot += self.game_config.bet_size;
+        self.reserve_gas();
+        let transition = self.save_rematch_acceptance(player);
+        self.schedule_timeout_check();
 
-            let part = exec::value_available() / players.len() as u128;
+        reply_ok(Event::RematchAccepted(transition), change);
 
-            for player in players.iter() {
-                msg::send(*player, "STOP", part).expect("Can't send reward");
-            }
+        Ok(())
+    }
+
+    fn forfeit(&mut self) -> Result<(), RpsError> {
+        let player = &msg::source();
+
+        self.validate_game_is_not_abandoned()?;
+        self.validate_player_is_in_game(player)?;
+
+        self.remove_player_from_game(player);
+        let transition = self.resolve_stage_after_forfeit();
+
+        reply_ok(Event::PlayerForfeited(transition), 0);
+
+        Ok(())
+    }
+
+    fn stop_the_game(&mut self) -> Result<(), RpsError> {
+        let players = self.return_bets();
+
+        reply_ok(Event::GameStopped(players), 0);
+
+        self.finish_game(None);
+
+        Ok(())
+    }
+
+    fn claim(&mut self) -> Result<(), RpsError> {
+        let value = self
+            .pending_withdrawals
+            .remove(&msg::source())
+            .ok_or(RpsError::NothingToClaim)?;
+
+        reply_ok(Event::Claimed(value), value);
+
+        Ok(())
+    }
+
+    fn withdraw_fees(&mut self) -> Result<(), RpsError> {
+        if self.fees.is_empty() {
+            return Err(RpsError::NoFees);
+        }
+
+        let fees: Vec<(ActorId, u128)> = self.fees.iter().map(|(k, v)| (*k, *v)).collect();
+        let mut total = 0;
+        for (treasury, value) in fees {
+            msg::send_bytes(treasury, [], value).map_err(|_| RpsError::FeesNotSent)?;
+            self.fees.remove(&treasury);
+            total += value;
+        }
+
+        reply_ok(Event::FeesWithdrawn(total), 0);
+
+        Ok(())
+    }
+
+    fn set_series(&mut self, series: Option<SeriesLength>) -> Result<(), RpsError> {
+        self.series = series;
+
+        reply_ok(Event::SeriesSet, 0);
+
+        Ok(())
+    }
+
+    fn join_series(&mut self) -> Result<(), RpsError> {
+        if self.series.is_none() {
+            return Err(RpsError::SeriesNotActive);
+        }
 
-            players
+        if msg::value() == 0 {
+            return Err(RpsError::ZeroValue);
+        }
+
+        let balance = self.series_balances.entry(msg::source()).or_default();
+        *balance += msg::value();
+
+        reply_ok(Event::JoinedSeries(*balance), 0);
+
+        Ok(())
+    }
+
+    fn leave_series(&mut self) -> Result<(), RpsError> {
+        let balance = self
+            .series_balances
+            .remove(&msg::source())
+            .ok_or(RpsError::NotInSeries)?;
+
+        reply_ok(Event::LeftSeries(balance), balance);
+
+        Ok(())
+    }
+
+    fn add_invitees(&mut self, invitees: Vec<ActorId>) -> Result<(), RpsError> {
+        self.validate_game_is_not_in_progress()?;
+
+        self.game_config
+            .allowlist
+            .get_or_insert_with(Default::default)
+            .extend(invitees);
+        self.remove_uninvited_players();
+
+        reply_ok(Event::AllowlistUpdated, 0);
+
+        Ok(())
+    }
+
+    fn remove_invitees(&mut self, invitees: Vec<ActorId>) -> Result<(), RpsError> {
+        self.validate_game_is_not_in_progress()?;
+
+        let allowlist = self
+            .game_config
+            .allowlist
+            .as_mut()
+            .ok_or(RpsError::LobbyIsPublic)?;
+        for invitee in invitees.iter() {
+            allowlist.remove(invitee);
+        }
+        self.remove_uninvited_players();
+
+        reply_ok(Event::AllowlistUpdated, 0);
+
+        Ok(())
+    }
+
+    fn kick_player(&mut self, player: ActorId) -> Result<(), RpsError> {
+        self.validate_game_is_not_abandoned()?;
+        self.validate_game_is_not_in_progress()?;
+        self.validate_player_is_in_lobby(&player)?;
+
+        self.lobby.remove(&player);
+        self.credit(player, self.game_config.bet_size);
+        msg::send(player, Event::PlayerKicked(player), 0).expect("Can't notify the player");
+
+        reply_ok(Event::PlayerKicked(player), 0);
+
+        Ok(())
+    }
+
+    fn set_game_over_listener(&mut self, listener: Option<ActorId>) -> Result<(), RpsError> {
+        self.game_over_listener = listener;
+
+        reply_ok(Event::GameOverListenerSet, 0);
+
+        Ok(())
+    }
+
+    fn advance_stage(&mut self) -> Result<(), RpsError> {
+        let Some(transition) = self.change_stage_by_timeout_if_needed() else {
+            self.validate_game_is_not_abandoned()?;
+            return Err(RpsError::DeadlineNotReached);
         };
 
-        msg::reply(Event::GameStopped(players), 0).expect("Reply error");
+        reply_ok(Event::StageAdvanced(transition), 0);
 
-        self.start_new_game();
+        Ok(())
+    }
+
+    fn check_timeout(&mut self, stage_nonce: u64) -> Result<(), RpsError> {
+        // The stage timeout has been applied before the action processing
+        // unless the message has come before the deadline or the program is paused.
+        // The check is scheduled again when the program is unpaused.
+        if stage_nonce == self.stage_nonce {
+            self.scheduled_check_nonce = None;
+            if !self.paused {
+                self.schedule_timeout_check();
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -97,38 +637,64 @@ extern "C" fn init() {
     let config: GameConfig = msg::load().expect("Could not load Action");
     debug!("init(): {:?}", config);
 
-    validate_game_config(&config);
+    config.validate().expect("Invalid game config");
 
-    let game = RPSGame {
+    let mut contract = Contract {
         owner: msg::source(),
-        game_config: config,
-        current_stage_start_timestamp: exec::block_timestamp(),
+        next_game_id: INIT_GAME_ID,
         ..Default::default()
     };
+    contract.insert_game(config);
 
-    unsafe { RPS_GAME = Some(game) };
+    unsafe { CONTRACT = Some(contract) };
 }
 
 #[no_mangle]
 extern "C" fn handle() {
     let action: Action = msg::load().expect("Could not load Action");
-    let game: &mut RPSGame = unsafe { RPS_GAME.get_or_insert(RPSGame::default()) };
+    let contract: &mut Contract = unsafe { CONTRACT.get_or_insert(Contract::default()) };
 
-    game.change_stage_by_timeout_if_needed();
-
-    match action {
-        Action::Register => game.register(),
-        Action::MakeMove(hashed_move) => game.make_move(hashed_move),
-        Action::Reveal(real_move) => game.reveal(real_move),
-        Action::ChangeNextGameConfig(config) => game.set_next_game_config(config),
-        Action::StopGame => game.stop_the_game(),
+    if let Err(error) = contract.process(action) {
+        msg::reply(Err::<Event, _>(error), msg::value()).expect("Can't send reply");
     }
 }
 
+fn reply_ok(event: Event, value: u128) {
+    msg::reply(Ok::<_, RpsError>(event), value).expect("Can't send reply");
+}
+
 fn common_state() -> <ContractMetadata as Metadata>::State {
-    let state = static_mut_state();
-    let RPSGame {
+    let Contract {
         owner,
+        pending_owner,
+        roles,
+        paused,
+        bet_limits,
+        blocklist,
+        games,
+        next_game_id,
+        ..
+    } = static_mut_state();
+
+    let games = games
+        .iter()
+        .map(|(game_id, game)| (*game_id, game_state(game)))
+        .collect();
+
+    ContractState {
+        owner: *owner,
+        pending_owner: *pending_owner,
+        roles: roles.iter().map(|(k, v)| (*k, *v)).collect(),
+        paused: *paused,
+        bet_limits: *bet_limits,
+        blocklist: blocklist.iter().map(|(k, v)| (*k, *v)).collect(),
+        games,
+        next_game_id: *next_game_id,
+    }
+}
+
+fn game_state(game: &RPSGame) -> GameState {
+    let RPSGame {
         lobby,
         game_config,
         stage,
@@ -136,14 +702,27 @@ fn common_state() -> <ContractMetadata as Metadata>::State {
         player_moves,
         next_game_config,
         current_stage_start_timestamp,
-    } = state;
+        current_stage_start_block,
+        round,
+        stage_nonce,
+        idle_extensions,
+        abandoned,
+        pot,
+        pending_withdrawals,
+        fees,
+        series,
+        series_balances,
+        game_over_listener,
+        ..
+    } = game;
 
     let encrypted_moves = encrypted_moves.iter().map(|(k, v)| (*k, *v)).collect();
     let player_moves = player_moves.iter().map(|(k, v)| (*k, v.clone())).collect();
     let lobby = lobby.iter().cloned().collect();
+    let pending_withdrawals = pending_withdrawals.iter().map(|(k, v)| (*k, *v)).collect();
+    let series_balances = series_balances.iter().map(|(k, v)| (*k, *v)).collect();
 
-    ContractState {
-        owner: *owner,
+    GameState {
         lobby,
         game_config: game_config.clone(),
         stage: stage.clone(),
@@ -151,11 +730,22 @@ fn common_state() -> <ContractMetadata as Metadata>::State {
         player_moves,
         next_game_config: next_game_config.clone(),
         current_stage_start_timestamp: *current_stage_start_timestamp,
+        current_stage_start_block: *current_stage_start_block,
+        round: *round,
+        stage_nonce: *stage_nonce,
+        idle_extensions: *idle_extensions,
+        abandoned: *abandoned,
+        pot: *pot,
+        pending_withdrawals,
+        fees: fees.iter().map(|(k, v)| (*k, *v)).collect(),
+        series: series.clone(),
+        series_balances,
+        game_over_listener: *game_over_listener,
     }
 }
 
-fn static_mut_state() -> &'static RPSGame {
-    unsafe { RPS_GAME.get_or_insert(Default::default()) }
+fn static_mut_state() -> &'static Contract {
+    unsafe { CONTRACT.get_or_insert(Default::default()) }
 }
 
 #[no_mangle]
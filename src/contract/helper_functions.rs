@@ -1,25 +1,43 @@
 use crate::contract::RPSGame;
-use gstd::{exec, msg, prelude::*, ActorId};
+use gstd::{exec, msg, prelude::*, ActorId, ReservationId};
 use rps_io::*;
 
+/// The timeout check comes a block later than the deadline has passed,
+/// so the players' actions and `Action::AdvanceStage` sent in time are processed first.
+const TIMEOUT_CHECK_GRACE_BLOCKS: u32 = 1;
+const RESERVATION_AMOUNT: u64 = 10_000_000_000;
+const MAX_RESERVATIONS_COUNT: usize = 16;
+
 impl RPSGame {
-    pub(crate) fn change_stage_by_timeout_if_needed(&mut self) {
-        let end_time = self.current_stage_start_timestamp
-            + match self.stage {
-                GameStage::Preparation => self.game_config.entry_timeout_ms,
-                GameStage::InProgress(_) => self.game_config.move_timeout_ms,
-                GameStage::Reveal(_) => self.game_config.reveal_timeout_ms,
-            };
+    pub(crate) fn stage_deadline(&self) -> Deadline {
+        self.game_config.stage_deadline(
+            &self.stage,
+            self.current_stage_start_timestamp,
+            self.current_stage_start_block,
+        )
+    }
 
-        if end_time < exec::block_timestamp() {
-            match &self.stage {
-                GameStage::Preparation => self.handle_preparation_timeout(),
-                GameStage::InProgress(_) => self.handle_moves_timeout(),
-                GameStage::Reveal(description) => {
-                    self.handle_reveal_timeout(description.finished_players.len())
-                }
-            }
+    /// The stage timeouts aren't applied while the program is paused.
+    pub(crate) fn change_stage_by_timeout_if_needed(&mut self) -> Option<StageTransition> {
+        if self.paused
+            || self.abandoned
+            || !self
+                .stage_deadline()
+                .has_passed(exec::block_timestamp(), exec::block_height())
+        {
+            return None;
         }
+
+        let transition = match &self.stage {
+            GameStage::Preparation => self.handle_preparation_timeout(),
+            GameStage::InProgress(_) => self.handle_moves_timeout(),
+            GameStage::Reveal(description) => {
+                self.handle_reveal_timeout(description.finished_players.len())
+            }
+            GameStage::Rematch(_) => self.handle_rematch_timeout(),
+        };
+
+        Some(transition)
     }
 
     pub(crate) fn transit_to_in_progress_stage_from_preparation(&mut self) {
@@ -33,6 +51,14 @@ impl RPSGame {
         self.update_timestamp();
     }
 
+    pub(crate) fn start_game_if_lobby_is_full(&mut self) {
+        if self.game_config.auto_start
+            && self.lobby.len() >= self.game_config.players_count_limit as usize
+        {
+            self.transit_to_in_progress_stage_from_preparation();
+        }
+    }
+
     pub(crate) fn try_to_transit_to_reveal_stage_after_move(&mut self) {
         if let GameStage::InProgress(description) = &self.stage {
             if description.anticipated_players.is_empty() {
@@ -41,37 +67,151 @@ impl RPSGame {
         }
     }
 
-    pub(crate) fn handle_preparation_timeout(&mut self) {
+    pub(crate) fn handle_preparation_timeout(&mut self) -> StageTransition {
         match self.lobby.len() {
-            0 | 1 => self.update_timestamp(),
-            _ => self.transit_to_in_progress_stage_from_preparation(),
+            0 => {
+                self.update_timestamp();
+                StageTransition::StageProlonged
+            }
+            count if count < self.game_config.min_players_to_start as usize => {
+                self.handle_idle_stage()
+            }
+            _ => {
+                self.transit_to_in_progress_stage_from_preparation();
+                StageTransition::GameStarted {
+                    players: self.lobby.iter().copied().collect(),
+                }
+            }
         }
     }
 
-    pub(crate) fn handle_moves_timeout(&mut self) {
+    pub(crate) fn handle_moves_timeout(&mut self) -> StageTransition {
         let finished_players = match &self.stage {
-            GameStage::Preparation | GameStage::Reveal(_) => panic!("Wrong stage"),
+            GameStage::Preparation | GameStage::Reveal(_) | GameStage::Rematch(_) => {
+                panic!("Wrong stage")
+            }
             GameStage::InProgress(description) => &description.finished_players,
         };
 
         match finished_players.len() {
-            0 => self.update_timestamp(),
+            0 => self.handle_idle_stage(),
             1 => {
-                let winner = finished_players.iter().last().expect("Unknown winner");
-                msg::send(*winner, "", exec::value_available()).expect("Can't send reward");
-                self.start_new_game();
+                let winner = *finished_players.iter().last().expect("Unknown winner");
+                self.pay_out(winner);
+                self.finish_game(Some(winner));
+                StageTransition::RoundEnded(RevealResult::GameOver { winner })
+            }
+            _ => {
+                let players = finished_players.clone();
+                self.transit_to_reveal_stage(players.clone());
+                StageTransition::RevealStarted { players }
             }
-            _ => self.transit_to_reveal_stage(finished_players.clone()),
         }
     }
 
-    pub(crate) fn handle_reveal_timeout(&mut self, finished_players_count: usize) {
+    pub(crate) fn handle_reveal_timeout(
+        &mut self,
+        finished_players_count: usize,
+    ) -> StageTransition {
         match finished_players_count {
-            0 => self.update_timestamp(),
-            _ => {
-                self.end_round();
+            0 => self.handle_idle_stage(),
+            _ => StageTransition::RoundEnded(self.end_round()),
+        }
+    }
+
+    /// The game starts as soon as enough players accept the rematch,
+    /// so the registration opens for everyone when the rematch timeout has passed.
+    pub(crate) fn handle_rematch_timeout(&mut self) -> StageTransition {
+        self.stage = GameStage::Preparation;
+        self.update_timestamp();
+        StageTransition::RematchDeclined
+    }
+
+    /// Prolongs the stage nobody has acted in or abandons the game according to the `AbandonPolicy`.
+    pub(crate) fn handle_idle_stage(&mut self) -> StageTransition {
+        let max_idle_extensions = match self.game_config.abandon_policy {
+            AbandonPolicy::Prolong => None,
+            AbandonPolicy::Refund {
+                max_idle_extensions,
+            }
+            | AbandonPolicy::OwnerResolves {
+                max_idle_extensions,
+            } => Some(max_idle_extensions),
+        };
+
+        if max_idle_extensions.is_some_and(|max| self.idle_extensions >= max) {
+            return self.abandon_game();
+        }
+
+        let idle_extensions = self.idle_extensions + 1;
+        self.update_timestamp();
+        self.idle_extensions = idle_extensions;
+
+        StageTransition::StageProlonged
+    }
+
+    pub(crate) fn abandon_game(&mut self) -> StageTransition {
+        let refunded_players = match self.game_config.abandon_policy {
+            AbandonPolicy::OwnerResolves { .. } => {
+                self.abandoned = true;
+                BTreeSet::new()
+            }
+            AbandonPolicy::Prolong | AbandonPolicy::Refund { .. } => {
+                let players = self.return_bets();
+                self.finish_game(None);
+                players
             }
+        };
+
+        StageTransition::GameAbandoned { refunded_players }
+    }
+
+    /// Returns the bets to the lobby during the registration
+    /// or splits the pot equally among the remaining players of the started game,
+    /// the remainder of the indivisible pot goes to the first of them.
+    /// The value is credited to `pending_withdrawals` to be claimed by the players.
+    pub(crate) fn return_bets(&mut self) -> BTreeSet<ActorId> {
+        let (players, part) = match self.stage.current_players() {
+            None => (
+                self.lobby.iter().copied().collect::<BTreeSet<_>>(),
+                self.game_config.bet_size,
+            ),
+            Some(players) => {
+                let part = self.pot / players.len() as u128;
+                (players, part)
+            }
+        };
+
+        for player in players.iter() {
+            self.credit(*player, part);
+        }
+
+        if let Some(first) = players.first() {
+            self.credit(*first, self.pot);
+        }
+
+        players
+    }
+
+    /// Keeps the fee of the config for its treasury and credits the rest of the pot to the winner.
+    pub(crate) fn pay_out(&mut self, winner: ActorId) {
+        let fee = self.game_config.fee(self.pot);
+        if fee != 0 {
+            self.pot -= fee;
+            *self.fees.entry(self.game_config.treasury).or_default() += fee;
+        }
+
+        self.credit(winner, self.pot);
+    }
+
+    /// Moves the value from the pot to `pending_withdrawals` of the player.
+    pub(crate) fn credit(&mut self, player: ActorId, value: u128) {
+        if value == 0 {
+            return;
         }
+
+        self.pot -= value;
+        *self.pending_withdrawals.entry(player).or_default() += value;
     }
 
     pub(crate) fn transit_to_reveal_stage(&mut self, next_round_players: BTreeSet<ActorId>) {
@@ -117,6 +257,7 @@ impl RPSGame {
             });
             self.update_timestamp();
             self.clear_moves();
+            self.round += 1;
 
             RevealResult::NextRoundStarted {
                 players: next_round_players,
@@ -126,13 +267,56 @@ impl RPSGame {
                 .into_iter()
                 .last()
                 .expect("Unknown winner");
-            msg::send(winner, "WIN", exec::value_available()).expect("Can't send reward");
-            self.start_new_game();
+            self.pay_out(winner);
+            self.finish_game(Some(winner));
 
             RevealResult::GameOver { winner }
         }
     }
 
+    /// Removes the player from the lobby as well, so they aren't offered the rematch.
+    pub(crate) fn remove_player_from_game(&mut self, player: &ActorId) {
+        self.lobby.remove(player);
+        if let GameStage::InProgress(description) | GameStage::Reveal(description) = &mut self.stage
+        {
+            description.anticipated_players.remove(player);
+            description.finished_players.remove(player);
+        }
+
+        self.encrypted_moves.remove(player);
+        self.player_moves.remove(player);
+    }
+
+    /// Ends the game if only one player remains
+    /// or the stage if all the remaining players have finished it.
+    pub(crate) fn resolve_stage_after_forfeit(&mut self) -> Option<StageTransition> {
+        let players = self
+            .stage
+            .current_players()
+            .expect("Game is not in progress");
+        if players.len() == 1 {
+            let winner = *players.iter().last().expect("Unknown winner");
+            self.pay_out(winner);
+            self.finish_game(Some(winner));
+
+            return Some(StageTransition::RoundEnded(RevealResult::GameOver {
+                winner,
+            }));
+        }
+
+        match &self.stage {
+            GameStage::InProgress(description) if description.anticipated_players.is_empty() => {
+                let players = description.finished_players.clone();
+                self.transit_to_reveal_stage(players.clone());
+                Some(StageTransition::RevealStarted { players })
+            }
+            GameStage::Reveal(description) if description.anticipated_players.is_empty() => {
+                Some(StageTransition::RoundEnded(self.end_round()))
+            }
+            _ => None,
+        }
+    }
+
     pub(crate) fn next_round_moves_set(&self, set_of_moves: BTreeSet<Move>) -> BTreeSet<Move> {
         'outer: for a_move in &set_of_moves {
             for b_move in &set_of_moves {
@@ -147,24 +331,20 @@ impl RPSGame {
         set_of_moves
     }
 
-    pub(crate) fn save_move(&mut self, player: &ActorId, move_hash: Vec<u8>) {
+    pub(crate) fn save_move(&mut self, player: &ActorId, move_hash: [u8; 32]) {
         if let GameStage::InProgress(progress_description) = &mut self.stage {
-            self.encrypted_moves
-                .insert(*player, move_hash.try_into().expect("wrong format"));
+            self.encrypted_moves.insert(*player, move_hash);
 
             progress_description.anticipated_players.remove(player);
             progress_description.finished_players.insert(*player);
         }
     }
 
-    pub(crate) fn save_real_move(&mut self, player: &ActorId, real_move: Vec<u8>) {
-        let users_move = real_move.first().expect("Move is empty");
-        let users_move = Move::new(*users_move);
-
+    pub(crate) fn save_real_move(&mut self, player: &ActorId, users_move: Move) {
         self.player_moves.insert(*player, users_move);
 
         match self.stage {
-            GameStage::Preparation | GameStage::InProgress(_) => {}
+            GameStage::Preparation | GameStage::InProgress(_) | GameStage::Rematch(_) => {}
             GameStage::Reveal(ref mut description) => {
                 description.anticipated_players.remove(player);
                 description.finished_players.insert(*player);
@@ -177,15 +357,160 @@ impl RPSGame {
         self.player_moves.clear();
     }
 
+    /// Notifies the game over listener and starts the next game in the same lobby.
+    /// The players can accept the rematch first if the game has a winner and it's not played in series.
+    pub(crate) fn finish_game(&mut self, winner: Option<ActorId>) {
+        if let Some(listener) = self.game_over_listener {
+            let notification = GameOverNotification {
+                game_id: self.game_id,
+                winner,
+            };
+            msg::send(listener, notification, 0).expect("Can't send notification");
+        }
+
+        let players: BTreeSet<ActorId> = self.lobby.iter().copied().collect();
+        self.start_new_game();
+        if winner.is_some() {
+            self.continue_series();
+        }
+
+        if winner.is_some() && self.series.is_none() && self.game_config.rematch_is_enabled() {
+            self.start_rematch(players);
+        }
+
+        self.unreserve_gas();
+    }
+
+    pub(crate) fn start_rematch(&mut self, players: BTreeSet<ActorId>) {
+        self.stage = GameStage::Rematch(StageDescription {
+            anticipated_players: players,
+            finished_players: Default::default(),
+        });
+        self.update_timestamp();
+    }
+
+    /// Starts the new game if `min_players_to_start` players have accepted the rematch.
+    pub(crate) fn save_rematch_acceptance(&mut self, player: &ActorId) -> Option<StageTransition> {
+        let GameStage::Rematch(description) = &mut self.stage else {
+            return None;
+        };
+
+        description.anticipated_players.remove(player);
+        description.finished_players.insert(*player);
+
+        if description.finished_players.len() < self.game_config.min_players_to_start as usize {
+            return None;
+        }
+
+        self.transit_to_in_progress_stage_from_preparation();
+
+        Some(StageTransition::GameStarted {
+            players: self.lobby.iter().copied().collect(),
+        })
+    }
+
     pub(crate) fn start_new_game(&mut self) {
         self.clear_for_new_game();
         self.stage = GameStage::Preparation;
         self.update_timestamp();
     }
 
+    /// Counts the finished game in the series and registers the players who have joined it for the next game
+    /// if the series goes on. The game starts at once if there are enough of them.
+    /// While the program is paused the registration is postponed until `resume`.
+    pub(crate) fn continue_series(&mut self) {
+        self.series = match self.series.take() {
+            Some(SeriesLength::Games(games)) if games > 1 => Some(SeriesLength::Games(games - 1)),
+            Some(SeriesLength::Unlimited) => Some(SeriesLength::Unlimited),
+            _ => None,
+        };
+
+        if self.series.is_none() {
+            return;
+        }
+
+        if self.paused {
+            self.series_registration_pending = true;
+        } else {
+            self.register_series_players();
+        }
+    }
+
+    /// Registers the players who have joined the series and have enough balance for the bet.
+    pub(crate) fn register_series_players(&mut self) {
+        self.series_registration_pending = false;
+
+        let bet = self.game_config.bet_size;
+        let mut players: Vec<ActorId> = self
+            .series_balances
+            .iter()
+            .filter(|(player, balance)| **balance >= bet && self.game_config.is_invited(player))
+            .map(|(player, _)| *player)
+            .collect();
+        players.sort();
+        players.truncate(self.game_config.players_count_limit as usize);
+
+        for player in players {
+            if let Some(balance) = self.series_balances.get_mut(&player) {
+                *balance -= bet;
+            }
+            self.lobby.insert(player);
+            self.pot += bet;
+        }
+
+        if self.lobby.len() >= self.game_config.min_players_to_start as usize {
+            self.transit_to_in_progress_stage_from_preparation();
+        } else {
+            self.schedule_timeout_check();
+        }
+    }
+
+    /// Removes the players who are not in the `allowlist` from the lobby and credits their bets back.
+    pub(crate) fn remove_uninvited_players(&mut self) {
+        let uninvited_players: Vec<ActorId> = self
+            .lobby
+            .iter()
+            .filter(|player| !self.game_config.is_invited(player))
+            .copied()
+            .collect();
+
+        for player in uninvited_players {
+            self.lobby.remove(&player);
+            self.credit(player, self.game_config.bet_size);
+        }
+    }
+
+    /// Credits the series balance of the player to `pending_withdrawals`.
+    pub(crate) fn remove_from_series(&mut self, player: &ActorId) {
+        if let Some(balance) = self.series_balances.remove(player) {
+            *self.pending_withdrawals.entry(*player).or_default() += balance;
+        }
+    }
+
+    /// Moves the current stage start forward by the time the program has been paused,
+    /// so the players have as much time left as they had when the pause began,
+    /// and catches up with the series registration and the timeout check postponed by the pause.
+    pub(crate) fn resume(&mut self, paused_at_timestamp: u64, paused_at_block: u32) {
+        self.paused = false;
+
+        let elapsed_ms = paused_at_timestamp.saturating_sub(self.current_stage_start_timestamp);
+        let elapsed_blocks = paused_at_block.saturating_sub(self.current_stage_start_block);
+        self.current_stage_start_timestamp = exec::block_timestamp() - elapsed_ms;
+        self.current_stage_start_block = exec::block_height() - elapsed_blocks;
+
+        if self.series_registration_pending && matches!(self.stage, GameStage::Preparation) {
+            self.register_series_players();
+        }
+        self.schedule_timeout_check();
+    }
+
+    /// Resets the `pot`, the bets of the finished game have been paid out or returned by now.
     pub(crate) fn clear_for_new_game(&mut self) {
         self.clear_moves();
         self.lobby.clear();
+        self.pot = 0;
+        self.round += 1;
+        self.abandoned = false;
         if let Some(config) = self.next_game_config.take() {
             self.game_config = config;
         }
@@ -193,5 +518,94 @@ impl RPSGame {
 
     pub(crate) fn update_timestamp(&mut self) {
         self.current_stage_start_timestamp = exec::block_timestamp();
+        self.current_stage_start_block = exec::block_height();
+        self.stage_nonce += 1;
+        self.idle_extensions = 0;
+        self.schedule_timeout_check();
+    }
+
+    /// Reserves gas from the current message to process timeouts of the following stages.
+    /// The reservation lives long enough for the check of the current stage, e.g. until a far `starts_at`,
+    /// and of the longest stage of the current and the next config.
+    pub(crate) fn reserve_gas(&mut self) {
+        if self.reservations.len() >= MAX_RESERVATIONS_COUNT {
+            return;
+        }
+
+        let max_timeout_blocks = self
+            .next_game_config
+            .as_ref()
+            .map_or(0, GameConfig::max_timeout_blocks)
+            .max(self.game_config.max_timeout_blocks())
+            .max(self.blocks_until_deadline());
+        let duration = max_timeout_blocks.saturating_add(1 + TIMEOUT_CHECK_GRACE_BLOCKS);
+
+        if let Ok(reservation) = ReservationId::reserve(RESERVATION_AMOUNT, duration) {
+            self.reservations
+                .push((reservation, exec::block_height().saturating_add(duration)));
+        }
+    }
+
+    /// The number of whole blocks left until the current stage deadline,
+    /// the timestamp deadlines are converted with `block_time_ms` of the config.
+    pub(crate) fn blocks_until_deadline(&self) -> u32 {
+        match self.stage_deadline() {
+            Deadline::Timestamp(deadline) => {
+                let blocks = deadline.saturating_sub(exec::block_timestamp())
+                    / self.game_config.block_time_ms;
+                u32::try_from(blocks).unwrap_or(u32::MAX)
+            }
+            Deadline::BlockHeight(deadline) => deadline.saturating_sub(exec::block_height()),
+        }
+    }
+
+    /// Returns the gas of the reservations that haven't been used for the timeout checks.
+    pub(crate) fn unreserve_gas(&mut self) {
+        for (reservation, _) in mem::take(&mut self.reservations) {
+            // The reservation may have expired already.
+            let _ = reservation.unreserve();
+        }
+    }
+
+    /// Sends `Action::CheckTimeout` to the program itself to be processed right after the current stage deadline
+    /// if it hasn't been sent for this stage yet.
+    /// If the reservation expires earlier, e.g. the deadline has been moved by the pause,
+    /// the check is sent for the last block of the reservation and schedules the next one when it comes.
+    /// Does nothing if there are no reservations left, then the stage can be changed by `Action::AdvanceStage`.
+    pub(crate) fn schedule_timeout_check(&mut self) {
+        if self.abandoned
+            || self.scheduled_check_nonce == Some(self.stage_nonce)
+            || (matches!(self.stage, GameStage::Preparation) && self.lobby.is_empty())
+        {
+            return;
+        }
+
+        let delay = self
+            .blocks_until_deadline()
+            .saturating_add(1 + TIMEOUT_CHECK_GRACE_BLOCKS);
+        let action = Action::CheckTimeout {
+            game_id: self.game_id,
+            stage_nonce: self.stage_nonce,
+        };
+
+        while let Some((reservation, expires_at)) = self.reservations.pop() {
+            let lifetime = expires_at.saturating_sub(exec::block_height());
+            if lifetime == 0 {
+                continue;
+            }
+
+            let result = msg::send_delayed_from_reservation(
+                reservation,
+                exec::program_id(),
+                &action,
+                0,
+                delay.min(lifetime),
+            );
+
+            if result.is_ok() {
+                self.scheduled_check_nonce = Some(self.stage_nonce);
+                break;
+            }
+        }
     }
 }
@@ -1,102 +1,247 @@
-use crate::contract::RPSGame;
+use crate::contract::{Contract, RPSGame};
 use gstd::{exec, msg, ActorId};
 use rps_io::*;
 
-const MIN_TIMEOUT_MS: u64 = 5000;
-const MIN_PLAYERS_COUNT: u8 = 2;
+impl Contract {
+    pub(crate) fn validate_source_is_owner(&self) -> Result<(), RpsError> {
+        if msg::source() != self.owner {
+            return Err(RpsError::NotOwner);
+        }
+
+        Ok(())
+    }
+
+    /// The owner has all the permissions of the admins.
+    pub(crate) fn validate_source_is_admin(&self) -> Result<(), RpsError> {
+        let source = msg::source();
+
+        if source != self.owner && self.roles.get(&source) != Some(&Role::Admin) {
+            return Err(RpsError::NotAdmin);
+        }
+
+        Ok(())
+    }
+
+    /// The owner and the admins have all the permissions of the operators.
+    pub(crate) fn validate_source_is_operator(&self) -> Result<(), RpsError> {
+        let source = msg::source();
+
+        if source != self.owner && !self.roles.contains_key(&source) {
+            return Err(RpsError::NotOperator);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_source_is_pending_owner(&self) -> Result<(), RpsError> {
+        if self.pending_owner != Some(msg::source()) {
+            return Err(RpsError::NotPendingOwner);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_player_is_not_blocked(&self, player: &ActorId) -> Result<(), RpsError> {
+        if self.is_blocked(player) {
+            return Err(RpsError::PlayerBlocked);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_not_paused(&self) -> Result<(), RpsError> {
+        if self.paused {
+            return Err(RpsError::Paused);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_game_config(&self, config: &GameConfig) -> Result<(), RpsError> {
+        config.validate()?;
+        self.bet_limits.validate_bet(config.bet_size)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_source_is_program(&self) -> Result<(), RpsError> {
+        if msg::source() != exec::program_id() {
+            return Err(RpsError::InternalAction);
+        }
+
+        Ok(())
+    }
+}
 
 impl RPSGame {
-    pub(crate) fn validate_there_is_place_for_player(&self) {
-        if self.lobby.len() + 1 > self.game_config.players_count_limit as usize {
-            panic!("There are enough players")
+    pub(crate) fn validate_config_fits_lobby(&self, config: &GameConfig) -> Result<(), RpsError> {
+        if (config.players_count_limit as usize) < self.lobby.len() {
+            return Err(ConfigError::PlayersCountLimitBelowLobby.into());
         }
+
+        Ok(())
     }
 
-    pub(crate) fn validate_source_is_owner(&self) {
-        if msg::source() != self.owner {
-            panic!("Caller is not an owner")
+    pub(crate) fn validate_there_is_place_for_player(&self) -> Result<(), RpsError> {
+        if self.lobby.len() + 1 > self.game_config.players_count_limit as usize {
+            return Err(RpsError::LobbyIsFull);
         }
+
+        Ok(())
     }
 
-    pub(crate) fn validate_there_is_no_such_player(&self, player: &ActorId) {
+    pub(crate) fn validate_there_is_no_such_player(
+        &self,
+        player: &ActorId,
+    ) -> Result<(), RpsError> {
         if self.lobby.contains(player) {
-            panic!("This player is already in lobby")
+            return Err(RpsError::PlayerAlreadyRegistered);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_player_is_invited(&self, player: &ActorId) -> Result<(), RpsError> {
+        if !self.game_config.is_invited(player) {
+            return Err(RpsError::NotInvited);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_player_is_in_lobby(&self, player: &ActorId) -> Result<(), RpsError> {
+        if !self.lobby.contains(player) {
+            return Err(RpsError::PlayerNotInGame);
         }
+
+        Ok(())
     }
 
-    pub(crate) fn validate_game_is_not_in_progress(&self) {
+    pub(crate) fn validate_player_is_in_game(&self, player: &ActorId) -> Result<(), RpsError> {
+        if !self.stage.is_player_in_game(player) {
+            return Err(RpsError::PlayerNotInGame);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_game_is_not_in_progress(&self) -> Result<(), RpsError> {
         if self.stage.game_is_in_progress() {
-            panic!("Game is in progress")
+            return Err(RpsError::GameIsInProgress);
         }
+
+        Ok(())
     }
 
-    pub(crate) fn validate_bet(&self, value: u128) {
+    pub(crate) fn validate_game_is_not_abandoned(&self) -> Result<(), RpsError> {
+        if self.abandoned {
+            return Err(RpsError::GameAbandoned);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_registration_is_open(&self) -> Result<(), RpsError> {
+        if self
+            .game_config
+            .registration_opens_at
+            .is_some_and(|registration_opens_at| registration_opens_at > exec::block_timestamp())
+        {
+            return Err(RpsError::RegistrationNotOpen);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_bet(&self, value: u128) -> Result<(), RpsError> {
         if self.game_config.bet_size > value {
-            panic!("Not enough money for bet")
+            return Err(RpsError::NotEnoughValue);
         }
+
+        Ok(())
     }
 
-    pub(crate) fn validate_player_can_make_a_move(&self, player: &ActorId) {
+    pub(crate) fn validate_player_can_make_a_move(&self, player: &ActorId) -> Result<(), RpsError> {
         match &self.stage {
             GameStage::InProgress(description) => {
                 if !description.anticipated_players.contains(player) {
-                    panic!("There is no such player in game right now, may be he got out of the game or he is not in the lobby")
+                    return Err(RpsError::PlayerNotInGame);
                 }
             }
-            GameStage::Reveal(_) | GameStage::Preparation => {
-                panic!(
-                    "It's not time to make a move, {:?}, {:?}, {:?}",
-                    self.stage,
-                    exec::block_timestamp(),
-                    self.current_stage_start_timestamp,
-                );
+            GameStage::Preparation | GameStage::Reveal(_) | GameStage::Rematch(_) => {
+                return Err(RpsError::NotMoveStage)
             }
         };
+
+        Ok(())
     }
 
-    pub(crate) fn validate_player_can_reveal(&self, player: &ActorId) {
+    pub(crate) fn validate_commitment_is_unique(
+        &self,
+        move_hash: &[u8; 32],
+    ) -> Result<(), RpsError> {
+        if self.encrypted_moves.values().any(|hash| hash == move_hash) {
+            return Err(RpsError::DuplicateCommitment);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_player_can_reveal(&self, player: &ActorId) -> Result<(), RpsError> {
         match &self.stage {
-            GameStage::Preparation | GameStage::InProgress(_) => panic!("It's not reveal stage!"),
+            GameStage::Preparation | GameStage::InProgress(_) | GameStage::Rematch(_) => {
+                return Err(RpsError::NotRevealStage)
+            }
             GameStage::Reveal(description) => {
                 if !description.anticipated_players.contains(player) {
                     if description.finished_players.contains(player) {
-                        panic!("Player has already revealed")
+                        return Err(RpsError::PlayerAlreadyRevealed);
                     } else {
-                        panic!("There is no such player at the reveal stage")
+                        return Err(RpsError::PlayerNotInGame);
                     }
                 }
             }
         };
+
+        Ok(())
     }
 
-    pub(crate) fn validate_reveal(&self, player: &ActorId, real_move: &[u8]) {
+    pub(crate) fn validate_player_can_accept_rematch(
+        &self,
+        player: &ActorId,
+    ) -> Result<(), RpsError> {
+        let GameStage::Rematch(description) = &self.stage else {
+            return Err(RpsError::NotRematchStage);
+        };
+
+        if !description.anticipated_players.contains(player) {
+            if description.finished_players.contains(player) {
+                return Err(RpsError::PlayerAlreadyRegistered);
+            } else {
+                return Err(RpsError::PlayerNotInGame);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_reveal(
+        &self,
+        player: &ActorId,
+        real_move: &[u8],
+    ) -> Result<(), RpsError> {
         let saved_move_bytes = self
             .encrypted_moves
             .get(player)
-            .expect("Can't find a move of this player");
+            .ok_or(RpsError::MoveNotFound)?;
 
-        let hash_bytes = sp_core_hashing::blake2_256(real_move);
+        let preimage = commitment_preimage(real_move, player, self.game_id, self.round);
+        let hash_bytes = sp_core_hashing::blake2_256(&preimage);
 
         if &hash_bytes != saved_move_bytes {
-            panic!("Player tries to cheat")
+            return Err(RpsError::MoveMismatch);
         }
-    }
-}
-
-pub(crate) fn validate_game_config(config: &GameConfig) {
-    if config.players_count_limit < MIN_PLAYERS_COUNT {
-        panic!("Players count limit is too low")
-    }
-
-    if config.entry_timeout_ms < MIN_TIMEOUT_MS {
-        panic!("Entry timeout is too low")
-    }
-
-    if config.move_timeout_ms < MIN_TIMEOUT_MS {
-        panic!("Move timeout is too low")
-    }
 
-    if config.reveal_timeout_ms < MIN_TIMEOUT_MS {
-        panic!("Reveal timeout is too low")
+        Ok(())
     }
 }
@@ -6,19 +6,115 @@ use rps_io::*;
 pub mod metafns {
     pub type State = <ContractMetadata as Metadata>::State;
 
-    pub fn config(state: State) -> GameConfig {
-        state.game_config
+    pub fn role_holders(role: Role, state: State) -> Vec<ActorId> {
+        state
+            .roles
+            .into_iter()
+            .filter(|(_, holder_role)| *holder_role == role)
+            .map(|(holder, _)| holder)
+            .collect()
     }
 
-    pub fn lobby_list(state: State) -> Vec<ActorId> {
-        state.lobby
+    pub fn open_games(state: State) -> Vec<GameId> {
+        state
+            .games
+            .into_iter()
+            .filter(|(_, game)| matches!(game.stage, GameStage::Preparation) && !game.abandoned)
+            .map(|(game_id, _)| game_id)
+            .collect()
     }
 
-    pub fn game_stage(state: State) -> GameStage {
-        state.stage
+    pub fn claimable(player: ActorId, state: State) -> Vec<(GameId, u128)> {
+        state
+            .games
+            .into_iter()
+            .filter_map(|(game_id, game)| {
+                game.pending_withdrawals
+                    .into_iter()
+                    .find(|(id, _)| *id == player)
+                    .map(|(_, value)| (game_id, value))
+            })
+            .collect()
     }
 
-    pub fn current_stage_start_timestamp(state: State) -> u64 {
-        state.current_stage_start_timestamp
+    pub fn fees(game_id: GameId, state: State) -> Vec<(ActorId, u128)> {
+        state
+            .game(game_id)
+            .map(|game| game.fees.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn series_balance(game_id: GameId, player: ActorId, state: State) -> Option<u128> {
+        state.game(game_id).and_then(|game| {
+            game.series_balances
+                .iter()
+                .find(|(id, _)| *id == player)
+                .map(|(_, balance)| *balance)
+        })
+    }
+
+    pub fn is_blocked(player: ActorId, timestamp: u64, state: State) -> bool {
+        state.is_blocked(&player, timestamp)
+    }
+
+    pub fn game(game_id: GameId, state: State) -> Option<GameState> {
+        state.game(game_id).cloned()
+    }
+
+    pub fn config(game_id: GameId, state: State) -> Option<GameConfig> {
+        state.game(game_id).map(|game| game.game_config.clone())
+    }
+
+    pub fn lobby_list(game_id: GameId, state: State) -> Vec<ActorId> {
+        state
+            .game(game_id)
+            .map(|game| game.lobby.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn game_stage(game_id: GameId, state: State) -> Option<GameStage> {
+        state.game(game_id).map(|game| game.stage.clone())
+    }
+
+    pub fn current_stage_start_timestamp(game_id: GameId, state: State) -> Option<u64> {
+        state
+            .game(game_id)
+            .map(|game| game.current_stage_start_timestamp)
+    }
+
+    pub fn current_stage_start_block(game_id: GameId, state: State) -> Option<u32> {
+        state
+            .game(game_id)
+            .map(|game| game.current_stage_start_block)
+    }
+
+    pub fn current_stage_deadline(game_id: GameId, state: State) -> Option<Deadline> {
+        state.game(game_id).map(|game| {
+            game.game_config.stage_deadline(
+                &game.stage,
+                game.current_stage_start_timestamp,
+                game.current_stage_start_block,
+            )
+        })
+    }
+
+    /// The end of the current stage if the deadline is a timestamp, see `current_stage_deadline`.
+    pub fn current_stage_end_timestamp(game_id: GameId, state: State) -> Option<u64> {
+        let game = state.game(game_id)?;
+
+        match game.game_config.stage_deadline(
+            &game.stage,
+            game.current_stage_start_timestamp,
+            game.current_stage_start_block,
+        ) {
+            Deadline::Timestamp(deadline) => Some(deadline),
+            Deadline::BlockHeight(_) => None,
+        }
+    }
+
+    pub fn registration_opens_at(game_id: GameId, state: State) -> Option<u64> {
+        state
+            .game(game_id)
+            .and_then(|game| game.game_config.registration_opens_at)
     }
 }
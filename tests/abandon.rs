@@ -0,0 +1,217 @@
+use gstd::prelude::*;
+use gtest::{Program, System};
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+fn init_with_policy(sys: &System, abandon_policy: AbandonPolicy) -> Program {
+    common_init_with_owner_and_config(
+        sys,
+        USERS[0],
+        GameConfig {
+            abandon_policy,
+            ..COMMON_CONFIG
+        },
+    )
+}
+
+fn wait_for_deadline(sys: &System) {
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+}
+
+fn check_users_are_refunded(sys: &System, game: &Program, users: &[u64]) {
+    for user in users {
+        claim_winnings(sys, game, *user);
+        check_users_balance(sys, user, START_BALANCE);
+    }
+}
+
+#[test]
+fn check_lobby_refund_on_preparation() {
+    let sys = System::new();
+    let game = init_with_policy(
+        &sys,
+        AbandonPolicy::Refund {
+            max_idle_extensions: 1,
+        },
+    );
+    check_register_player(&game, USERS[1], COMMON_BET);
+
+    wait_for_deadline(&sys);
+    check_advance_stage(&game, USERS[2], StageTransition::StageProlonged);
+
+    wait_for_deadline(&sys);
+    check_advance_stage(
+        &game,
+        USERS[2],
+        StageTransition::GameAbandoned {
+            refunded_players: players(&USERS[1..2]),
+        },
+    );
+
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::Preparation));
+    assert!(state.lobby.is_empty());
+    check_users_are_refunded(&sys, &game, &USERS[1..2]);
+}
+
+#[test]
+fn check_players_refund_on_moves_stage() {
+    let sys = System::new();
+    let game = init_with_policy(
+        &sys,
+        AbandonPolicy::Refund {
+            max_idle_extensions: 1,
+        },
+    );
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    wait_for_deadline(&sys);
+    check_advance_stage(
+        &game,
+        USERS[3],
+        StageTransition::GameStarted {
+            players: players(COMMON_USERS_SET),
+        },
+    );
+
+    wait_for_deadline(&sys);
+    check_advance_stage(&game, USERS[3], StageTransition::StageProlonged);
+
+    wait_for_deadline(&sys);
+    check_advance_stage(
+        &game,
+        USERS[3],
+        StageTransition::GameAbandoned {
+            refunded_players: players(COMMON_USERS_SET),
+        },
+    );
+
+    check_users_are_refunded(&sys, &game, COMMON_USERS_SET);
+    check_register_player(&game, USERS[3], COMMON_BET);
+}
+
+#[test]
+fn check_players_refund_on_reveal_stage() {
+    let sys = System::new();
+    let game = init_with_policy(
+        &sys,
+        AbandonPolicy::Refund {
+            max_idle_extensions: 1,
+        },
+    );
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    wait_for_deadline(&sys);
+    reach_reveal_stage(
+        &game,
+        COMMON_USERS_SET,
+        &[Move::Rock, Move::Paper, Move::Scissors],
+    );
+
+    wait_for_deadline(&sys);
+    check_advance_stage(&game, USERS[3], StageTransition::StageProlonged);
+
+    wait_for_deadline(&sys);
+    check_advance_stage(
+        &game,
+        USERS[3],
+        StageTransition::GameAbandoned {
+            refunded_players: players(COMMON_USERS_SET),
+        },
+    );
+
+    failure_user_reveal(&game, USERS[0], Move::Rock, RpsError::NotRevealStage);
+    check_users_are_refunded(&sys, &game, COMMON_USERS_SET);
+}
+
+#[test]
+fn check_lobby_of_owner_resolved_game_is_kept() {
+    let sys = System::new();
+    let game = init_with_policy(
+        &sys,
+        AbandonPolicy::OwnerResolves {
+            max_idle_extensions: 0,
+        },
+    );
+    check_register_player(&game, USERS[1], COMMON_BET);
+
+    wait_for_deadline(&sys);
+    check_advance_stage(
+        &game,
+        USERS[2],
+        StageTransition::GameAbandoned {
+            refunded_players: BTreeSet::new(),
+        },
+    );
+
+    // the lobby stays as it is until the owner stops the game
+    failure_action(
+        &game,
+        USERS[1],
+        Action::LeaveLobby(GAME_ID),
+        RpsError::GameAbandoned,
+    );
+    failure_action(
+        &game,
+        USERS[0],
+        Action::KickPlayer(GAME_ID, USERS[1].into()),
+        RpsError::GameAbandoned,
+    );
+    assert_eq!(game_state(&game).lobby, vec![USERS[1].into()]);
+
+    check_stop_the_game(&game, USERS[0], &USERS[1..2]);
+    check_users_are_refunded(&sys, &game, &USERS[1..2]);
+}
+
+#[test]
+fn check_owner_resolves_abandoned_game() {
+    let sys = System::new();
+    let game = init_with_policy(
+        &sys,
+        AbandonPolicy::OwnerResolves {
+            max_idle_extensions: 0,
+        },
+    );
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    wait_for_deadline(&sys);
+    check_user_move(&game, USERS[0], Move::Rock);
+    check_user_move(&game, USERS[1], Move::Paper);
+
+    wait_for_deadline(&sys);
+    check_advance_stage(
+        &game,
+        USERS[3],
+        StageTransition::RevealStarted {
+            players: players(&USERS[0..2]),
+        },
+    );
+
+    wait_for_deadline(&sys);
+    check_advance_stage(
+        &game,
+        USERS[3],
+        StageTransition::GameAbandoned {
+            refunded_players: BTreeSet::new(),
+        },
+    );
+
+    let state = game_state(&game);
+    assert!(state.abandoned);
+
+    // nothing happens until the owner stops the game
+    wait_for_deadline(&sys);
+    failure_user_reveal(&game, USERS[0], Move::Rock, RpsError::GameAbandoned);
+    failure_register_player(&game, USERS[3], COMMON_BET, RpsError::GameAbandoned);
+    failure_advance_stage(&game, USERS[3], RpsError::GameAbandoned);
+
+    check_stop_the_game(&game, USERS[0], &USERS[0..2]);
+    check_register_player(&game, USERS[3], COMMON_BET);
+
+    claim_winnings(&sys, &game, USERS[0]);
+    claim_winnings(&sys, &game, USERS[1]);
+    check_users_balance(&sys, &USERS[0], START_BALANCE + COMMON_BET / 2);
+    check_users_balance(&sys, &USERS[1], START_BALANCE + COMMON_BET / 2);
+}
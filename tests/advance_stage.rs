@@ -0,0 +1,124 @@
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+#[test]
+fn check_game_start() {
+    let sys = System::new();
+    let game = common_init_and_register(&sys);
+
+    check_advance_stage(
+        &game,
+        USERS[3],
+        StageTransition::GameStarted {
+            players: players(COMMON_USERS_SET),
+        },
+    );
+
+    failure_register_player(&game, USERS[3], COMMON_BET, RpsError::GameIsInProgress);
+    check_user_move(&game, USERS[0], Move::Rock);
+}
+
+#[test]
+fn check_preparation_prolongation() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    check_register_player(&game, USERS[0], COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    check_advance_stage(&game, USERS[1], StageTransition::StageProlonged);
+    failure_advance_stage(&game, USERS[1], RpsError::DeadlineNotReached);
+    check_register_player(&game, USERS[1], COMMON_BET);
+}
+
+#[test]
+fn check_deadline_not_reached() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000));
+
+    failure_advance_stage(&game, USERS[3], RpsError::DeadlineNotReached);
+    check_register_player(&game, USERS[3], COMMON_BET);
+}
+
+#[test]
+fn check_transition_is_kept_after_failed_action() {
+    let sys = System::new();
+    let game = common_init_and_register(&sys);
+
+    failure_register_player(&game, USERS[3], COMMON_BET, RpsError::GameIsInProgress);
+    failure_advance_stage(&game, USERS[3], RpsError::DeadlineNotReached);
+
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::InProgress(_)));
+}
+
+#[test]
+fn check_reveal_start() {
+    let sys = System::new();
+    let game = common_init_and_register(&sys);
+
+    check_user_move(&game, USERS[0], Move::Rock);
+    check_user_move(&game, USERS[1], Move::Paper);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    check_advance_stage(
+        &game,
+        USERS[3],
+        StageTransition::RevealStarted {
+            players: players(&USERS[0..2]),
+        },
+    );
+
+    check_user_reveal_with_continue(&game, USERS[0], Move::Rock);
+    check_user_reveal_with_game_over(&game, USERS[1], Move::Paper, USERS[1].into());
+}
+
+#[test]
+fn check_game_over_on_moves_timeout() {
+    let sys = System::new();
+    let game = common_init_and_register(&sys);
+
+    check_user_move(&game, USERS[0], Move::Rock);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    check_advance_stage(
+        &game,
+        USERS[3],
+        StageTransition::RoundEnded(RevealResult::GameOver {
+            winner: USERS[0].into(),
+        }),
+    );
+
+    check_register_player(&game, USERS[3], COMMON_BET);
+}
+
+#[test]
+fn check_round_end_on_reveal_timeout() {
+    let sys = System::new();
+    let game = reach_reveal_stage_with_init(
+        &sys,
+        COMMON_USERS_SET,
+        &[Move::Rock, Move::Rock, Move::Rock],
+    );
+
+    check_user_reveal_with_continue(&game, USERS[0], Move::Rock);
+    check_user_reveal_with_continue(&game, USERS[1], Move::Rock);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    check_advance_stage(
+        &game,
+        USERS[3],
+        StageTransition::RoundEnded(RevealResult::NextRoundStarted {
+            players: players(&USERS[0..2]),
+        }),
+    );
+
+    check_user_move(&game, USERS[0], Move::Rock);
+    failure_user_move(&game, USERS[2], Move::Rock, RpsError::PlayerNotInGame);
+}
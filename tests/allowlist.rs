@@ -0,0 +1,94 @@
+use gstd::{prelude::*, ActorId};
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+fn invitees(users: &[u64]) -> Vec<ActorId> {
+    users.iter().copied().map(Into::into).collect()
+}
+
+#[test]
+fn check_private_lobby() {
+    let sys = System::new();
+    let game = common_init_with_owner_and_config(
+        &sys,
+        USERS[0],
+        GameConfig {
+            allowlist: Some(players(&USERS[0..2])),
+            ..COMMON_CONFIG
+        },
+    );
+
+    failure_register_player(&game, USERS[2], COMMON_BET, RpsError::NotInvited);
+    check_register_player(&game, USERS[0], COMMON_BET);
+    check_register_player(&game, USERS[1], COMMON_BET);
+
+    failure_action(
+        &game,
+        USERS[1],
+        Action::AddInvitees(GAME_ID, invitees(&USERS[2..3])),
+        RpsError::NotAdmin,
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::AddInvitees(GAME_ID, invitees(&USERS[2..3])),
+        Event::AllowlistUpdated,
+    );
+    check_register_player(&game, USERS[2], COMMON_BET);
+
+    // the removed player loses the place in the lobby and gets the bet back
+    check_action(
+        &game,
+        USERS[0],
+        Action::RemoveInvitees(GAME_ID, invitees(&USERS[1..2])),
+        Event::AllowlistUpdated,
+    );
+    failure_register_player(&game, USERS[1], COMMON_BET, RpsError::NotInvited);
+    claim_winnings(&sys, &game, USERS[1]);
+    check_users_balance(&sys, &USERS[1], START_BALANCE);
+
+    let state = game_state(&game);
+    assert_eq!(state.lobby.len(), 2);
+    assert_eq!(state.pot, COMMON_BET * 2);
+
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    failure_action(
+        &game,
+        USERS[0],
+        Action::AddInvitees(GAME_ID, invitees(&USERS[1..2])),
+        RpsError::GameIsInProgress,
+    );
+}
+
+#[test]
+fn check_public_lobby_becomes_private() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    register_players(&game, &USERS[1..3], COMMON_BET);
+
+    failure_action(
+        &game,
+        USERS[0],
+        Action::RemoveInvitees(GAME_ID, invitees(&USERS[1..2])),
+        RpsError::LobbyIsPublic,
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::AddInvitees(GAME_ID, invitees(&USERS[2..4])),
+        Event::AllowlistUpdated,
+    );
+
+    // the registered player who is not invited loses the place and gets the bet back
+    let state = game_state(&game);
+    assert_eq!(state.lobby, vec![USERS[2].into()]);
+    assert_eq!(state.pot, COMMON_BET);
+    claim_winnings(&sys, &game, USERS[1]);
+    check_users_balance(&sys, &USERS[1], START_BALANCE);
+
+    failure_register_player(&game, USERS[1], COMMON_BET, RpsError::NotInvited);
+    check_register_player(&game, USERS[3], COMMON_BET);
+}
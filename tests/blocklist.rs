@@ -0,0 +1,143 @@
+use gstd::Encode;
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+#[test]
+fn check_blocked_players_cant_register() {
+    let sys = System::new();
+    let game = common_init_with_owner_and_config(
+        &sys,
+        USERS[0],
+        GameConfig {
+            entry_timeout_ms: COMMON_TIMEOUT * 10,
+            ..COMMON_CONFIG
+        },
+    );
+    let init_timestamp = game_state(&game).current_stage_start_timestamp;
+    let expires_at = init_timestamp + COMMON_TIMEOUT * 2;
+
+    failure_action(
+        &game,
+        USERS[1],
+        Action::BlockPlayer(USERS[2].into(), None),
+        RpsError::NotOperator,
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::BlockPlayer(USERS[1].into(), None),
+        Event::PlayerBlocked,
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::BlockPlayer(USERS[2].into(), Some(expires_at)),
+        Event::PlayerBlocked,
+    );
+
+    failure_register_player(&game, USERS[1], COMMON_BET, RpsError::PlayerBlocked);
+    failure_register_player(&game, USERS[2], COMMON_BET, RpsError::PlayerBlocked);
+    check_register_player(&game, USERS[3], COMMON_BET);
+
+    let state = contract_state(&game);
+    assert!(state.is_blocked(&USERS[1].into(), u64::MAX));
+    assert!(state.is_blocked(&USERS[2].into(), expires_at - 1));
+    assert!(!state.is_blocked(&USERS[2].into(), expires_at));
+    assert!(!state.is_blocked(&USERS[3].into(), init_timestamp));
+
+    // the block expires
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT * 2 / 1_000));
+    check_register_player(&game, USERS[2], COMMON_BET);
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::UnblockPlayer(USERS[1].into()),
+        Event::PlayerUnblocked,
+    );
+    check_register_player(&game, USERS[1], COMMON_BET);
+    failure_action(
+        &game,
+        USERS[0],
+        Action::UnblockPlayer(USERS[3].into()),
+        RpsError::PlayerNotBlocked,
+    );
+}
+
+#[test]
+fn check_blocked_players_cant_join_any_game() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    check_action(
+        &game,
+        USERS[0],
+        Action::SetSeries(GAME_ID, Some(SeriesLength::Unlimited)),
+        Event::SeriesSet,
+    );
+    let result = game.send_with_value(USERS[1], Action::JoinSeries(GAME_ID), COMMON_BET);
+    assert!(result.contains(&(
+        USERS[1],
+        Ok::<_, RpsError>(Event::JoinedSeries(COMMON_BET)).encode()
+    )));
+
+    let result = game.send(USERS[0], Action::CreateGame(COMMON_CONFIG));
+    assert!(result.contains(&(USERS[0], Ok::<_, RpsError>(Event::GameCreated(1)).encode())));
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::BlockPlayer(USERS[1].into(), None),
+        Event::PlayerBlocked,
+    );
+
+    // the series balance is credited back
+    let state = game_state(&game);
+    assert!(state.series_balances.is_empty());
+    assert_eq!(
+        state.pending_withdrawals,
+        vec![(USERS[1].into(), COMMON_BET)]
+    );
+
+    failure_register_player(&game, USERS[1], COMMON_BET, RpsError::PlayerBlocked);
+    failure_action(
+        &game,
+        USERS[1],
+        Action::Register(1),
+        RpsError::PlayerBlocked,
+    );
+    failure_action(
+        &game,
+        USERS[1],
+        Action::JoinSeries(GAME_ID),
+        RpsError::PlayerBlocked,
+    );
+}
+
+#[test]
+fn check_expired_blocks_are_removed() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let expires_at = game_state(&game).current_stage_start_timestamp + COMMON_TIMEOUT;
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::BlockPlayer(USERS[1].into(), Some(expires_at)),
+        Event::PlayerBlocked,
+    );
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000));
+    check_action(
+        &game,
+        USERS[0],
+        Action::BlockPlayer(USERS[2].into(), None),
+        Event::PlayerBlocked,
+    );
+
+    assert_eq!(
+        contract_state(&game).blocklist,
+        vec![(USERS[2].into(), None)]
+    );
+}
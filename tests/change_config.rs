@@ -20,6 +20,7 @@ fn common() {
             entry_timeout_ms: COMMON_TIMEOUT * 2,
             move_timeout_ms: COMMON_TIMEOUT * 3,
             reveal_timeout_ms: COMMON_TIMEOUT * 4,
+            ..COMMON_CONFIG
         },
     );
 
@@ -35,29 +36,30 @@ fn common() {
     check_register_player(&game, USERS[1], 0);
     check_register_player(&game, USERS[2], 0);
     check_register_player(&game, USERS[3], 0);
-    failure_register_player(&game, USERS[0], 0);
+    failure_register_player(&game, USERS[0], 0, RpsError::LobbyIsFull);
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT * 2 / 1000));
-    failure_user_move(&game, USERS[1], Move::Rock);
+    failure_user_move(&game, USERS[1], Move::Rock, RpsError::NotMoveStage);
     sys.spend_blocks(1);
     check_user_move(&game, USERS[2], Move::Paper);
     check_user_move(&game, USERS[1], Move::Rock);
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT * 3 / 1000));
-    failure_user_reveal(&game, USERS[1], Move::Rock);
+    failure_user_reveal(&game, USERS[1], Move::Rock, RpsError::NotRevealStage);
     sys.spend_blocks(1);
     check_user_reveal_with_continue(&game, USERS[1], Move::Rock);
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT * 4 / 1000));
-    failure_register_player(&game, USERS[0], 0);
+    failure_register_player(&game, USERS[0], 0, RpsError::GameIsInProgress);
     sys.spend_blocks(1);
 
     check_register_player(&game, USERS[1], 0);
 }
 
-// checks that the config doesn't change immediately
+// checks that the config doesn't change immediately when someone has registered
 #[test]
 fn check_round_start() {
     let sys = System::new();
     let game = common_init(&sys);
+    check_register_player(&game, USERS[0], COMMON_BET);
     check_change_next_game_config(
         &game,
         USERS[0],
@@ -67,15 +69,37 @@ fn check_round_start() {
             entry_timeout_ms: COMMON_TIMEOUT * 2,
             move_timeout_ms: COMMON_TIMEOUT * 3,
             reveal_timeout_ms: COMMON_TIMEOUT * 4,
+            ..COMMON_CONFIG
         },
     );
 
-    check_register_player(&game, USERS[0], COMMON_BET);
     check_register_player(&game, USERS[1], COMMON_BET);
     check_register_player(&game, USERS[2], COMMON_BET);
     check_register_player(&game, USERS[3], COMMON_BET);
 }
 
+#[test]
+fn check_change_with_empty_lobby() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let config = GameConfig {
+        bet_size: 500,
+        players_count_limit: 3,
+        ..COMMON_CONFIG
+    };
+    check_change_current_game_config(&game, USERS[0], config.clone());
+    assert_eq!(game_state(&game).game_config, config);
+    assert_eq!(game_state(&game).next_game_config, None);
+
+    failure_register_player(&game, USERS[0], 0, RpsError::NotEnoughValue);
+    register_players(&game, COMMON_USERS_SET, 500);
+    failure_register_player(&game, USERS[3], 500, RpsError::LobbyIsFull);
+
+    // the lobby isn't empty anymore
+    check_change_next_game_config(&game, USERS[0], COMMON_CONFIG);
+    assert_eq!(game_state(&game).game_config, config);
+}
+
 #[test]
 fn check_two_times() {
     let sys = System::new();
@@ -90,6 +114,7 @@ fn check_two_times() {
             entry_timeout_ms: COMMON_TIMEOUT * 2,
             move_timeout_ms: COMMON_TIMEOUT * 3,
             reveal_timeout_ms: COMMON_TIMEOUT * 4,
+            ..COMMON_CONFIG
         },
     );
 
@@ -108,13 +133,14 @@ fn check_two_times() {
             entry_timeout_ms: COMMON_TIMEOUT,
             move_timeout_ms: COMMON_TIMEOUT,
             reveal_timeout_ms: COMMON_TIMEOUT,
+            ..COMMON_CONFIG
         },
     );
 
     check_register_player(&game, USERS[0], 0);
     check_register_player(&game, USERS[1], 0);
     check_register_player(&game, USERS[2], 0);
-    failure_register_player(&game, USERS[3], 0);
+    failure_register_player(&game, USERS[3], 0, RpsError::LobbyIsFull);
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT * 2 + 1));
 
     play_round(
@@ -123,7 +149,7 @@ fn check_two_times() {
         &[Move::Rock, Move::Paper, Move::Rock],
     );
 
-    failure_register_player(&game, USERS[0], 0);
+    failure_register_player(&game, USERS[0], 0, RpsError::NotEnoughValue);
     check_register_player(&game, USERS[0], 500);
     check_register_player(&game, USERS[1], 500);
     check_register_player(&game, USERS[2], 500);
@@ -144,6 +170,7 @@ fn check_twice_in_a_row() {
             entry_timeout_ms: COMMON_TIMEOUT * 2,
             move_timeout_ms: COMMON_TIMEOUT * 3,
             reveal_timeout_ms: COMMON_TIMEOUT * 4,
+            ..COMMON_CONFIG
         },
     );
 
@@ -156,6 +183,7 @@ fn check_twice_in_a_row() {
             entry_timeout_ms: COMMON_TIMEOUT,
             move_timeout_ms: COMMON_TIMEOUT,
             reveal_timeout_ms: COMMON_TIMEOUT,
+            ..COMMON_CONFIG
         },
     );
 
@@ -165,12 +193,12 @@ fn check_twice_in_a_row() {
         &[Move::Rock, Move::Paper, Move::Rock],
     );
 
-    failure_register_player(&game, USERS[0], 0);
+    failure_register_player(&game, USERS[0], 0, RpsError::NotEnoughValue);
     check_register_player(&game, USERS[0], 500);
     check_register_player(&game, USERS[1], 500);
     check_register_player(&game, USERS[2], 500);
     check_register_player(&game, USERS[3], 500);
-    failure_register_player(&game, USERS[3] + 1, 500);
+    failure_register_player(&game, USERS[3] + 1, 500, RpsError::LobbyIsFull);
 }
 
 #[test]
@@ -187,7 +215,9 @@ fn failure_with_wrong_timouts() {
             entry_timeout_ms: 4999,
             move_timeout_ms: COMMON_TIMEOUT,
             reveal_timeout_ms: COMMON_TIMEOUT,
+            ..COMMON_CONFIG
         },
+        RpsError::InvalidConfig(ConfigError::EntryTimeoutTooLow),
     );
     failure_change_next_game_config(
         &game,
@@ -198,7 +228,9 @@ fn failure_with_wrong_timouts() {
             entry_timeout_ms: COMMON_TIMEOUT,
             move_timeout_ms: 4999,
             reveal_timeout_ms: COMMON_TIMEOUT,
+            ..COMMON_CONFIG
         },
+        RpsError::InvalidConfig(ConfigError::MoveTimeoutTooLow),
     );
     failure_change_next_game_config(
         &game,
@@ -209,7 +241,9 @@ fn failure_with_wrong_timouts() {
             entry_timeout_ms: COMMON_TIMEOUT,
             move_timeout_ms: COMMON_TIMEOUT,
             reveal_timeout_ms: 4999,
+            ..COMMON_CONFIG
         },
+        RpsError::InvalidConfig(ConfigError::RevealTimeoutTooLow),
     );
 }
 
@@ -227,7 +261,20 @@ fn failure_with_not_owners_request() {
             entry_timeout_ms: COMMON_TIMEOUT,
             move_timeout_ms: COMMON_TIMEOUT,
             reveal_timeout_ms: COMMON_TIMEOUT,
+            ..COMMON_CONFIG
+        },
+        RpsError::NotAdmin,
+    );
+
+    // the permissions are checked before the config
+    failure_change_next_game_config(
+        &game,
+        USERS[1],
+        GameConfig {
+            players_count_limit: 1,
+            ..COMMON_CONFIG
         },
+        RpsError::NotAdmin,
     );
 }
 
@@ -236,7 +283,7 @@ fn failure_with_inappropriate_users_limit() {
     let sys = System::new();
     let game = common_init(&sys);
 
-    check_change_next_game_config(
+    check_change_current_game_config(
         &game,
         USERS[0],
         GameConfig {
@@ -245,6 +292,7 @@ fn failure_with_inappropriate_users_limit() {
             entry_timeout_ms: COMMON_TIMEOUT,
             move_timeout_ms: COMMON_TIMEOUT,
             reveal_timeout_ms: COMMON_TIMEOUT,
+            ..COMMON_CONFIG
         },
     );
 
@@ -257,6 +305,172 @@ fn failure_with_inappropriate_users_limit() {
             entry_timeout_ms: COMMON_TIMEOUT,
             move_timeout_ms: COMMON_TIMEOUT,
             reveal_timeout_ms: COMMON_TIMEOUT,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::PlayersCountLimitTooLow),
+    );
+}
+
+#[test]
+fn check_min_players_to_start_bounds() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            min_players_to_start: 1,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::MinPlayersToStartTooLow),
+    );
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            players_count_limit: 3,
+            min_players_to_start: 4,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::MinPlayersToStartAboveLimit),
+    );
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            players_count_limit: 3,
+            min_players_to_start: 3,
+            ..COMMON_CONFIG
+        },
+    );
+}
+
+#[test]
+fn check_upper_bounds() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let week_ms = 7 * 24 * 60 * 60 * 1000;
+
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            players_count_limit: 51,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::PlayersCountLimitTooHigh),
+    );
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            move_timeout_ms: week_ms + 1,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::MoveTimeoutTooHigh),
+    );
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            rematch_timeout_ms: week_ms + 1,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::RematchTimeoutTooHigh),
+    );
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            players_count_limit: 50,
+            entry_timeout_ms: week_ms,
+            ..COMMON_CONFIG
+        },
+    );
+}
+
+#[test]
+fn failure_with_limit_below_lobby() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            players_count_limit: 2,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::PlayersCountLimitBelowLobby),
+    );
+    check_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            players_count_limit: 3,
+            ..COMMON_CONFIG
+        },
+    );
+}
+
+#[test]
+fn check_bet_limits() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let bet_limits = BetLimits {
+        min: COMMON_BET,
+        max: COMMON_BET * 10,
+    };
+
+    failure_action(
+        &game,
+        USERS[1],
+        Action::SetBetLimits(bet_limits),
+        RpsError::NotOwner,
+    );
+    failure_action(
+        &game,
+        USERS[0],
+        Action::SetBetLimits(BetLimits {
+            min: COMMON_BET + 1,
+            max: COMMON_BET,
+        }),
+        RpsError::InvalidConfig(ConfigError::InvalidBetLimits),
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::SetBetLimits(bet_limits),
+        Event::BetLimitsSet,
+    );
+    assert_eq!(contract_state(&game).bet_limits, bet_limits);
+
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            bet_size: COMMON_BET - 1,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::BetTooLow),
+    );
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            bet_size: COMMON_BET * 10 + 1,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::BetTooHigh),
+    );
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            bet_size: COMMON_BET * 10,
+            ..COMMON_CONFIG
         },
     );
 }
@@ -0,0 +1,66 @@
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+#[test]
+fn check_winner_claims_pot() {
+    let sys = System::new();
+    let moves = [Move::Rock, Move::Rock, Move::Paper];
+
+    let game = reach_reveal_stage_with_init(&sys, COMMON_USERS_SET, &moves);
+    check_user_reveal_with_continue(&game, USERS[0], moves[0].clone());
+    check_user_reveal_with_continue(&game, USERS[1], moves[1].clone());
+    check_user_reveal_with_game_over(&game, USERS[2], moves[2].clone(), USERS[2].into());
+
+    // nothing is pushed to the winner until the claim
+    check_users_balance(&sys, &USERS[2], START_BALANCE - COMMON_BET);
+    assert_eq!(
+        game_state(&game).pending_withdrawals,
+        vec![(USERS[2].into(), COMMON_BET * 3)]
+    );
+
+    failure_action(
+        &game,
+        USERS[0],
+        Action::Claim(GAME_ID),
+        RpsError::NothingToClaim,
+    );
+    check_action(
+        &game,
+        USERS[2],
+        Action::Claim(GAME_ID),
+        Event::Claimed(COMMON_BET * 3),
+    );
+    sys.claim_value_from_mailbox(USERS[2]);
+    check_users_balance(&sys, &USERS[2], START_BALANCE + COMMON_BET * 2);
+    assert!(game_state(&game).pending_withdrawals.is_empty());
+
+    failure_action(
+        &game,
+        USERS[2],
+        Action::Claim(GAME_ID),
+        RpsError::NothingToClaim,
+    );
+}
+
+#[test]
+fn check_refunds_are_claimable() {
+    let sys = System::new();
+    let game = init_and_register_with_users(&sys, COMMON_USERS_SET);
+
+    check_stop_the_game(&game, USERS[0], COMMON_USERS_SET);
+    assert_eq!(game_state(&game).pending_withdrawals.len(), 3);
+
+    for user in COMMON_USERS_SET {
+        check_action(
+            &game,
+            *user,
+            Action::Claim(GAME_ID),
+            Event::Claimed(COMMON_BET),
+        );
+        sys.claim_value_from_mailbox(*user);
+        check_users_balance(&sys, user, START_BALANCE);
+    }
+}
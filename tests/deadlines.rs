@@ -0,0 +1,195 @@
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+const BLOCK_TIMEOUTS: BlockTimeouts = BlockTimeouts {
+    entry_timeout_blocks: 5,
+    move_timeout_blocks: 5,
+    reveal_timeout_blocks: 5,
+    rematch_timeout_blocks: 0,
+};
+
+#[test]
+fn check_block_timeouts() {
+    let sys = System::new();
+    let game = common_init_with_owner_and_config(
+        &sys,
+        USERS[0],
+        GameConfig {
+            block_timeouts: Some(BLOCK_TIMEOUTS),
+            ..COMMON_CONFIG
+        },
+    );
+
+    let state = game_state(&game);
+    assert_eq!(
+        state.game_config.stage_deadline(
+            &state.stage,
+            state.current_stage_start_timestamp,
+            state.current_stage_start_block
+        ),
+        Deadline::BlockHeight(state.current_stage_start_block + 5)
+    );
+
+    register_players(&game, &USERS[0..3], COMMON_BET);
+    failure_user_move(&game, USERS[0], Move::Spock, RpsError::NotMoveStage);
+
+    sys.spend_blocks(BLOCK_TIMEOUTS.entry_timeout_blocks);
+    failure_user_move(&game, USERS[0], Move::Spock, RpsError::NotMoveStage);
+    sys.spend_blocks(1);
+    check_user_move(&game, USERS[0], Move::Spock);
+    check_user_move(&game, USERS[1], Move::Spock);
+
+    failure_user_reveal(&game, USERS[0], Move::Spock, RpsError::NotRevealStage);
+    sys.spend_blocks(BLOCK_TIMEOUTS.move_timeout_blocks);
+    failure_user_reveal(&game, USERS[0], Move::Spock, RpsError::NotRevealStage);
+    sys.spend_blocks(1);
+    check_user_reveal_with_continue(&game, USERS[0], Move::Spock);
+    sys.spend_blocks(BLOCK_TIMEOUTS.reveal_timeout_blocks);
+    sys.spend_blocks(1);
+
+    register_players(&game, &USERS[0..3], COMMON_BET);
+}
+
+#[test]
+fn check_duration_timeout() {
+    let sys = System::new();
+    let minute = Duration {
+        days: 0,
+        hours: 0,
+        minutes: 1,
+    };
+    let game = common_init_with_owner_and_config(
+        &sys,
+        USERS[0],
+        GameConfig {
+            entry_timeout_ms: minute.as_millis().unwrap(),
+            ..COMMON_CONFIG
+        },
+    );
+
+    register_players(&game, &USERS[0..3], COMMON_BET);
+    sys.spend_blocks(minute.as_blocks(1_000).unwrap());
+    failure_user_move(&game, USERS[0], Move::Rock, RpsError::NotMoveStage);
+    sys.spend_blocks(1);
+    check_user_move(&game, USERS[0], Move::Rock);
+}
+
+#[test]
+fn check_duration_conversions() {
+    let duration = Duration {
+        days: 1,
+        hours: 2,
+        minutes: 3,
+    };
+
+    assert_eq!(duration.as_millis(), Ok(((24 + 2) * 60 + 3) * 60 * 1000));
+    assert_eq!(duration.as_blocks(1_000), Ok(((24 + 2) * 60 + 3) * 60));
+    assert_eq!(
+        Duration {
+            days: 0,
+            hours: 0,
+            minutes: 1
+        }
+        .as_blocks(7_000),
+        Ok(9)
+    );
+    assert_eq!(duration.as_blocks(0), Err(ConfigError::ZeroBlockTime));
+
+    let long_duration = Duration {
+        days: u64::MAX / 24,
+        hours: 0,
+        minutes: 0,
+    };
+    assert_eq!(
+        long_duration.as_millis(),
+        Err(ConfigError::DurationOverflow)
+    );
+    assert_eq!(
+        Duration {
+            days: 50_000,
+            hours: 0,
+            minutes: 0
+        }
+        .as_blocks(1),
+        Err(ConfigError::DurationOverflow)
+    );
+}
+
+#[test]
+fn check_block_timeouts_validation() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            block_timeouts: Some(BlockTimeouts {
+                move_timeout_blocks: 4,
+                ..BLOCK_TIMEOUTS
+            }),
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::BlockTimeoutTooLow),
+    );
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            block_timeouts: Some(BlockTimeouts {
+                rematch_timeout_blocks: 7 * 24 * 60 * 60 + 1,
+                ..BLOCK_TIMEOUTS
+            }),
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::BlockTimeoutTooHigh),
+    );
+    // the `*_timeout_ms` are not used with the block timeouts
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            entry_timeout_ms: 0,
+            block_timeouts: Some(BLOCK_TIMEOUTS),
+            ..COMMON_CONFIG
+        },
+    );
+}
+
+#[test]
+fn check_block_time_validation() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            block_time_ms: 0,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::ZeroBlockTime),
+    );
+    // the timeout check of a 1000 seconds stage is 1000000 blocks later
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            move_timeout_ms: 1_000_000,
+            block_time_ms: 1,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::TimeoutTooManyBlocks),
+    );
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            block_time_ms: 3_000,
+            ..COMMON_CONFIG
+        },
+    );
+}
@@ -0,0 +1,96 @@
+use gstd::Encode;
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+fn wait_for_stage_timeout(sys: &System) {
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 2));
+}
+
+#[test]
+fn check_game_start_without_interaction() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    wait_for_stage_timeout(&sys);
+
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::InProgress(_)));
+    failure_advance_stage(&game, USERS[3], RpsError::DeadlineNotReached);
+}
+
+#[test]
+fn check_moves_timeout_payout_without_interaction() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    wait_for_stage_timeout(&sys);
+    check_user_move(&game, USERS[0], Move::Rock);
+    wait_for_stage_timeout(&sys);
+
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::Preparation));
+    assert!(state.lobby.is_empty());
+
+    claim_winnings(&sys, &game, USERS[0]);
+    check_users_balance(&sys, &USERS[0], START_BALANCE + COMMON_BET * 2);
+}
+
+#[test]
+fn check_reveal_timeout_payout_without_interaction() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    wait_for_stage_timeout(&sys);
+    check_user_move(&game, USERS[0], Move::Rock);
+    check_user_move(&game, USERS[1], Move::Scissors);
+    wait_for_stage_timeout(&sys);
+    check_user_reveal_with_continue(&game, USERS[0], Move::Rock);
+    wait_for_stage_timeout(&sys);
+
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::Preparation));
+
+    claim_winnings(&sys, &game, USERS[0]);
+    check_users_balance(&sys, &USERS[0], START_BALANCE + COMMON_BET * 2);
+}
+
+#[test]
+fn check_stale_timeout_check_is_ignored() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    register_players(&game, &USERS[0..2], COMMON_BET);
+
+    sys.spend_blocks(2);
+    check_stop_the_game(&game, USERS[0], &USERS[0..2]);
+    register_players(&game, &USERS[0..2], COMMON_BET);
+
+    // the timeout check scheduled before the game was stopped comes in the meantime
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000));
+    check_register_player(&game, USERS[2], COMMON_BET);
+
+    sys.spend_blocks(2);
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::InProgress(_)));
+}
+
+#[test]
+fn check_timeout_from_user() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    let result = game.send(
+        USERS[0],
+        Action::CheckTimeout {
+            game_id: GAME_ID,
+            stage_nonce: 0,
+        },
+    );
+
+    assert!(result.contains(&(USERS[0], Err::<Event, _>(RpsError::InternalAction).encode())));
+}
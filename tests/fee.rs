@@ -0,0 +1,149 @@
+use gtest::{Program, System};
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+const FEE_BPS: u16 = 250;
+const TREASURY: u64 = 10;
+
+fn init_with_fee(sys: &System) -> Program {
+    common_init_with_owner_and_config(
+        sys,
+        USERS[0],
+        GameConfig {
+            fee_bps: FEE_BPS,
+            treasury: TREASURY.into(),
+            ..COMMON_CONFIG
+        },
+    )
+}
+
+#[test]
+fn check_fee_is_kept_from_payout() {
+    let sys = System::new();
+    let game = init_with_fee(&sys);
+    let moves = [Move::Rock, Move::Rock, Move::Paper];
+    let pot = COMMON_BET * 3;
+    let fee = pot * FEE_BPS as u128 / 10_000;
+
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    reach_reveal_stage(&game, COMMON_USERS_SET, &moves);
+    check_user_reveal_with_continue(&game, USERS[0], moves[0].clone());
+    check_user_reveal_with_continue(&game, USERS[1], moves[1].clone());
+    check_user_reveal_with_game_over(&game, USERS[2], moves[2].clone(), USERS[2].into());
+
+    let state = game_state(&game);
+    assert_eq!(state.fees, vec![(TREASURY.into(), fee)]);
+    assert_eq!(
+        state.pending_withdrawals,
+        vec![(USERS[2].into(), pot - fee)]
+    );
+
+    failure_action(
+        &game,
+        USERS[1],
+        Action::WithdrawFees(GAME_ID),
+        RpsError::NotOwner,
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::WithdrawFees(GAME_ID),
+        Event::FeesWithdrawn(fee),
+    );
+    failure_action(
+        &game,
+        USERS[0],
+        Action::WithdrawFees(GAME_ID),
+        RpsError::NoFees,
+    );
+
+    sys.claim_value_from_mailbox(TREASURY);
+    assert_eq!(sys.balance_of(TREASURY), fee);
+}
+
+#[test]
+fn check_refunds_are_made_without_fee() {
+    let sys = System::new();
+    let game = init_with_fee(&sys);
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    check_stop_the_game(&game, USERS[0], COMMON_USERS_SET);
+    assert!(game_state(&game).fees.is_empty());
+    failure_action(
+        &game,
+        USERS[0],
+        Action::WithdrawFees(GAME_ID),
+        RpsError::NoFees,
+    );
+}
+
+#[test]
+fn check_fees_go_to_their_treasury() {
+    let sys = System::new();
+    let game = init_with_fee(&sys);
+    let fee = COMMON_BET * 2 * FEE_BPS as u128 / 10_000;
+
+    register_players(&game, &USERS[0..2], COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    play_round(&game, &USERS[0..2], &[Move::Rock, Move::Paper]);
+
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            fee_bps: FEE_BPS,
+            treasury: (TREASURY + 1).into(),
+            ..COMMON_CONFIG
+        },
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::WithdrawFees(GAME_ID),
+        Event::FeesWithdrawn(fee),
+    );
+
+    sys.claim_value_from_mailbox(TREASURY);
+    sys.claim_value_from_mailbox(TREASURY + 1);
+    assert_eq!(sys.balance_of(TREASURY), fee);
+    assert_eq!(sys.balance_of(TREASURY + 1), 0);
+}
+
+#[test]
+fn check_fee_is_bounded() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            fee_bps: 1_001,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::FeeTooHigh),
+    );
+    // the fees can't be kept for the zero address
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            fee_bps: 1_000,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::ZeroTreasury),
+    );
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            fee_bps: 1_000,
+            treasury: TREASURY.into(),
+            ..COMMON_CONFIG
+        },
+    );
+}
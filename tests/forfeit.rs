@@ -0,0 +1,86 @@
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+#[test]
+fn check_forfeit_during_moves() {
+    let sys = System::new();
+    let game = init_and_register_with_users(&sys, USERS);
+
+    check_user_move(&game, USERS[0], Move::Rock);
+    check_forfeit(&game, USERS[3], None);
+    check_user_move(&game, USERS[1], Move::Paper);
+
+    // the stage waited only for the forfeited player
+    check_forfeit(
+        &game,
+        USERS[2],
+        Some(StageTransition::RevealStarted {
+            players: players(&USERS[0..2]),
+        }),
+    );
+
+    failure_user_move(&game, USERS[2], Move::Rock, RpsError::NotMoveStage);
+    check_user_reveal_with_continue(&game, USERS[0], Move::Rock);
+    check_user_reveal_with_game_over(&game, USERS[1], Move::Paper, USERS[1].into());
+
+    claim_winnings(&sys, &game, USERS[1]);
+    check_users_balance(&sys, &USERS[1], START_BALANCE + COMMON_BET * 3);
+}
+
+#[test]
+fn check_forfeit_during_reveal() {
+    let sys = System::new();
+    let moves = [Move::Rock, Move::Paper, Move::Scissors, Move::Spock];
+    let game = reach_reveal_stage_with_init(&sys, USERS, &moves);
+
+    check_user_reveal_with_continue(&game, USERS[0], moves[0].clone());
+    check_forfeit(&game, USERS[3], None);
+    check_user_reveal_with_continue(&game, USERS[1], moves[1].clone());
+    failure_forfeit(&game, USERS[3], RpsError::PlayerNotInGame);
+
+    // the round is played out without the moves of the forfeited players
+    check_forfeit(
+        &game,
+        USERS[2],
+        Some(StageTransition::RoundEnded(RevealResult::GameOver {
+            winner: USERS[1].into(),
+        })),
+    );
+
+    claim_winnings(&sys, &game, USERS[1]);
+    check_users_balance(&sys, &USERS[1], START_BALANCE + COMMON_BET * 3);
+}
+
+#[test]
+fn check_last_player_wins() {
+    let sys = System::new();
+    let game = init_and_register_with_users(&sys, &USERS[0..2]);
+
+    check_forfeit(
+        &game,
+        USERS[0],
+        Some(StageTransition::RoundEnded(RevealResult::GameOver {
+            winner: USERS[1].into(),
+        })),
+    );
+    assert!(matches!(game_state(&game).stage, GameStage::Preparation));
+
+    claim_winnings(&sys, &game, USERS[1]);
+    check_users_balance(&sys, &USERS[1], START_BALANCE + COMMON_BET);
+}
+
+#[test]
+fn check_forfeit_not_in_game() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    check_register_player(&game, USERS[0], COMMON_BET);
+    failure_forfeit(&game, USERS[0], RpsError::PlayerNotInGame);
+
+    check_register_player(&game, USERS[1], COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    failure_forfeit(&game, USERS[2], RpsError::PlayerNotInGame);
+}
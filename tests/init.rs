@@ -28,6 +28,7 @@ pub fn init(
             entry_timeout_ms,
             move_timeout_ms,
             reveal_timeout_ms,
+            ..COMMON_CONFIG
         },
     );
 
@@ -54,24 +55,24 @@ fn check_all_users_bet() {
     );
 
     register_players(&game, &USERS[0..3], COMMON_BET);
-    failure_register_player(&game, USERS[3], COMMON_BET - 1);
-    failure_user_move(&game, USERS[0], Move::Spock);
+    failure_register_player(&game, USERS[3], COMMON_BET - 1, RpsError::NotEnoughValue);
+    failure_user_move(&game, USERS[0], Move::Spock, RpsError::NotMoveStage);
 
     sys.spend_blocks(blocks_count(entry_timout_ms / 1_000));
-    failure_user_move(&game, USERS[0], Move::Spock);
+    failure_user_move(&game, USERS[0], Move::Spock, RpsError::NotMoveStage);
     sys.spend_blocks(1);
     check_user_move(&game, USERS[0], Move::Spock);
     check_user_move(&game, USERS[1], Move::Spock);
-    failure_user_move(&game, USERS[1], Move::Lizard);
-    failure_user_move(&game, USERS[3], Move::Spock);
+    failure_user_move(&game, USERS[1], Move::Lizard, RpsError::PlayerNotInGame);
+    failure_user_move(&game, USERS[3], Move::Spock, RpsError::PlayerNotInGame);
 
-    failure_user_reveal(&game, USERS[0], Move::Spock);
+    failure_user_reveal(&game, USERS[0], Move::Spock, RpsError::NotRevealStage);
     sys.spend_blocks(blocks_count(move_timout_ms / 1_000));
-    failure_user_reveal(&game, USERS[0], Move::Spock);
+    failure_user_reveal(&game, USERS[0], Move::Spock, RpsError::NotRevealStage);
     sys.spend_blocks(1);
     check_user_reveal_with_continue(&game, USERS[0], Move::Spock);
-    failure_user_reveal(&game, USERS[2], Move::Lizard);
-    failure_user_reveal(&game, USERS[1], Move::Lizard);
+    failure_user_reveal(&game, USERS[2], Move::Lizard, RpsError::PlayerNotInGame);
+    failure_user_reveal(&game, USERS[1], Move::Lizard, RpsError::MoveMismatch);
     sys.spend_blocks(blocks_count(reveal_timout_ms / 1_000));
     sys.spend_blocks(1);
 
@@ -0,0 +1,62 @@
+use gtest::{Log, System};
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+#[test]
+fn check_kicked_player_is_refunded() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    failure_action(
+        &game,
+        USERS[1],
+        Action::KickPlayer(GAME_ID, USERS[2].into()),
+        RpsError::NotOperator,
+    );
+    failure_action(
+        &game,
+        USERS[0],
+        Action::KickPlayer(GAME_ID, USERS[3].into()),
+        RpsError::PlayerNotInGame,
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::KickPlayer(GAME_ID, USERS[2].into()),
+        Event::PlayerKicked(USERS[2].into()),
+    );
+
+    assert!(sys.get_mailbox(USERS[2]).contains(
+        &Log::builder()
+            .dest(USERS[2])
+            .payload(Event::PlayerKicked(USERS[2].into()))
+    ));
+
+    let state = game_state(&game);
+    assert_eq!(state.lobby.len(), 2);
+    assert_eq!(state.pot, COMMON_BET * 2);
+
+    claim_winnings(&sys, &game, USERS[2]);
+    check_users_balance(&sys, &USERS[2], START_BALANCE);
+
+    // only the kicked player has left the lobby
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    check_user_move(&game, USERS[0], Move::Rock);
+    failure_user_move(&game, USERS[2], Move::Rock, RpsError::PlayerNotInGame);
+}
+
+#[test]
+fn check_kick_player_in_progress() {
+    let sys = System::new();
+    let game = common_init_and_register(&sys);
+
+    failure_action(
+        &game,
+        USERS[0],
+        Action::KickPlayer(GAME_ID, USERS[1].into()),
+        RpsError::GameIsInProgress,
+    );
+}
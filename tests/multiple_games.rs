@@ -0,0 +1,136 @@
+use gstd::Encode;
+use gtest::{Program, System};
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+fn register_in_game(program: &Program, game_id: GameId, users: &[u64], bet: u128) {
+    for user in users {
+        check_action_with_value(
+            program,
+            *user,
+            Action::Register(game_id),
+            bet,
+            Event::PlayerRegistered,
+        );
+    }
+}
+
+#[test]
+fn check_create_game() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    failure_action(
+        &game,
+        USERS[1],
+        Action::CreateGame(COMMON_CONFIG),
+        RpsError::NotAdmin,
+    );
+
+    let invalid_config = GameConfig {
+        players_count_limit: 1,
+        ..COMMON_CONFIG
+    };
+    failure_action(
+        &game,
+        USERS[1],
+        Action::CreateGame(invalid_config.clone()),
+        RpsError::NotAdmin,
+    );
+    failure_action(
+        &game,
+        USERS[0],
+        Action::CreateGame(invalid_config),
+        RpsError::InvalidConfig(ConfigError::PlayersCountLimitTooLow),
+    );
+
+    let config = GameConfig {
+        bet_size: COMMON_BET * 2,
+        ..COMMON_CONFIG
+    };
+    check_action(
+        &game,
+        USERS[0],
+        Action::CreateGame(config.clone()),
+        Event::GameCreated(1),
+    );
+
+    let state: ContractState = game.read_state().expect("Not suitable reply");
+    assert_eq!(state.next_game_id, 2);
+    assert_eq!(state.game(1).expect("Game not found").game_config, config);
+}
+
+#[test]
+fn check_unknown_game() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    let result = game.send_with_value(USERS[1], Action::Register(1), COMMON_BET);
+
+    assert!(result.contains(&(USERS[1], Err::<Event, _>(RpsError::GameNotFound).encode())));
+    sys.claim_value_from_mailbox(USERS[1]);
+    check_users_balance(&sys, &USERS[1], START_BALANCE);
+}
+
+#[test]
+fn check_concurrent_games_have_separate_pots() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    check_action(
+        &game,
+        USERS[0],
+        Action::CreateGame(GameConfig {
+            bet_size: COMMON_BET * 2,
+            ..COMMON_CONFIG
+        }),
+        Event::GameCreated(1),
+    );
+
+    register_in_game(&game, GAME_ID, &USERS[0..2], COMMON_BET);
+    register_in_game(&game, 1, &USERS[2..4], COMMON_BET * 2);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    check_user_move_in_game(&game, GAME_ID, USERS[0], Move::Rock);
+    check_user_move_in_game(&game, GAME_ID, USERS[1], Move::Scissors);
+    check_user_move_in_game(&game, 1, USERS[2], Move::Rock);
+    check_user_move_in_game(&game, 1, USERS[3], Move::Paper);
+
+    try_to_reveal_in_game(&game, GAME_ID, USERS[0], Move::Rock);
+    let result = try_to_reveal_in_game(&game, GAME_ID, USERS[1], Move::Scissors);
+    assert!(result.contains(&(
+        USERS[1],
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::GameOver {
+            winner: USERS[0].into()
+        }))
+        .encode()
+    )));
+
+    claim_winnings(&sys, &game, USERS[0]);
+    check_users_balance(&sys, &USERS[0], START_BALANCE + COMMON_BET);
+
+    let state: ContractState = game.read_state().expect("Not suitable reply");
+    let other_game = state.game(1).expect("Game not found");
+    assert!(matches!(other_game.stage, GameStage::Reveal(_)));
+    assert_eq!(other_game.pot, COMMON_BET * 4);
+
+    try_to_reveal_in_game(&game, 1, USERS[2], Move::Rock);
+    let result = try_to_reveal_in_game(&game, 1, USERS[3], Move::Paper);
+    assert!(result.contains(&(
+        USERS[3],
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::GameOver {
+            winner: USERS[3].into()
+        }))
+        .encode()
+    )));
+
+    check_action(
+        &game,
+        USERS[3],
+        Action::Claim(1),
+        Event::Claimed(COMMON_BET * 4),
+    );
+    sys.claim_value_from_mailbox(USERS[3]);
+    check_users_balance(&sys, &USERS[3], START_BALANCE + COMMON_BET * 2);
+}
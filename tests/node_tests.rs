@@ -26,6 +26,7 @@ async fn init() -> Result<()> {
         entry_timeout_ms,
         move_timeout_ms,
         reveal_timeout_ms,
+        ..COMMON_CONFIG
     };
 
     let init_payload = init.encode();
@@ -0,0 +1,75 @@
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+#[test]
+fn check_ownership_transfer() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    failure_action(
+        &game,
+        USERS[1],
+        Action::ProposeOwner(USERS[1].into()),
+        RpsError::NotOwner,
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::ProposeOwner(USERS[1].into()),
+        Event::OwnerProposed(USERS[1].into()),
+    );
+    assert_eq!(contract_state(&game).pending_owner, Some(USERS[1].into()));
+
+    // the owner doesn't change until the proposal is accepted
+    failure_stop_the_game(&game, USERS[1], RpsError::NotOperator);
+    failure_action(
+        &game,
+        USERS[2],
+        Action::AcceptOwnership,
+        RpsError::NotPendingOwner,
+    );
+
+    check_action(
+        &game,
+        USERS[1],
+        Action::AcceptOwnership,
+        Event::OwnershipTransferred(USERS[1].into()),
+    );
+    let state = contract_state(&game);
+    assert_eq!(state.owner, USERS[1].into());
+    assert_eq!(state.pending_owner, None);
+
+    failure_stop_the_game(&game, USERS[0], RpsError::NotOperator);
+    check_stop_the_game(&game, USERS[1], &[]);
+}
+
+#[test]
+fn check_ownership_renounce() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::ProposeOwner(USERS[1].into()),
+        Event::OwnerProposed(USERS[1].into()),
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::RenounceOwnership,
+        Event::OwnershipRenounced,
+    );
+
+    // the proposal is canceled with the renounce
+    failure_action(
+        &game,
+        USERS[1],
+        Action::AcceptOwnership,
+        RpsError::NotPendingOwner,
+    );
+    failure_stop_the_game(&game, USERS[0], RpsError::NotOperator);
+}
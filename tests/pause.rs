@@ -0,0 +1,84 @@
+use gtest::{Program, System};
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+fn is_paused(program: &Program) -> bool {
+    contract_state(program).paused
+}
+
+#[test]
+fn check_pause_blocks_registration() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    check_register_player(&game, USERS[0], COMMON_BET);
+
+    failure_action(&game, USERS[1], Action::Pause, RpsError::NotOwner);
+    check_action(&game, USERS[0], Action::Pause, Event::Paused);
+    failure_action(&game, USERS[0], Action::Pause, RpsError::Paused);
+    assert!(is_paused(&game));
+
+    failure_register_player(&game, USERS[1], COMMON_BET, RpsError::Paused);
+
+    // the registered player can still leave the lobby
+    check_leave_lobby(&game, USERS[0]);
+
+    check_action(&game, USERS[0], Action::Unpause, Event::Unpaused);
+    failure_action(&game, USERS[0], Action::Unpause, RpsError::NotPaused);
+    assert!(!is_paused(&game));
+    check_register_player(&game, USERS[1], COMMON_BET);
+}
+
+#[test]
+fn check_pause_allows_reveal() {
+    let sys = System::new();
+    let moves = [Move::Rock, Move::Paper, Move::Rock];
+    let game = init_and_register_with_users(&sys, COMMON_USERS_SET);
+
+    check_user_move(&game, USERS[0], moves[0].clone());
+    check_action(&game, USERS[0], Action::Pause, Event::Paused);
+    failure_user_move(&game, USERS[1], moves[1].clone(), RpsError::Paused);
+    check_action(&game, USERS[0], Action::Unpause, Event::Unpaused);
+    check_user_move(&game, USERS[1], moves[1].clone());
+    check_user_move(&game, USERS[2], moves[2].clone());
+
+    check_action(&game, USERS[0], Action::Pause, Event::Paused);
+    check_user_reveal_with_continue(&game, USERS[0], moves[0].clone());
+    check_user_reveal_with_continue(&game, USERS[1], moves[1].clone());
+    check_user_reveal_with_game_over(&game, USERS[2], moves[2].clone(), USERS[1].into());
+
+    claim_winnings(&sys, &game, USERS[1]);
+    check_users_balance(&sys, &USERS[1], START_BALANCE + COMMON_BET * 2);
+}
+
+#[test]
+fn check_pause_stops_deadlines() {
+    let sys = System::new();
+    let moves = [Move::Rock, Move::Paper, Move::Rock];
+    let game = init_and_register_with_users(&sys, COMMON_USERS_SET);
+
+    check_user_move(&game, USERS[0], moves[0].clone());
+    check_action(&game, USERS[0], Action::Pause, Event::Paused);
+
+    // the scheduled timeout check comes during the pause
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 3));
+    failure_action(
+        &game,
+        USERS[1],
+        Action::AdvanceStage(GAME_ID),
+        RpsError::Paused,
+    );
+
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::InProgress(_)));
+    assert!(state.pending_withdrawals.is_empty());
+    assert_eq!(state.pot, COMMON_BET * 3);
+
+    // the players have the whole move timeout again after the pause
+    check_action(&game, USERS[0], Action::Unpause, Event::Unpaused);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 - 1));
+    check_user_move(&game, USERS[1], moves[1].clone());
+    check_user_move(&game, USERS[2], moves[2].clone());
+    assert!(matches!(game_state(&game).stage, GameStage::Reveal(_)));
+}
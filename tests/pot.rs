@@ -0,0 +1,152 @@
+use gstd::Encode;
+use gtest::{Program, System};
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+/// Checks that the program balance is the pots, pending withdrawals, fees and series balances of all games
+/// plus the untracked surplus.
+fn check_balance_invariant(sys: &System, program: &Program, surplus: u128) {
+    let state: ContractState = program.read_state().expect("Not suitable reply");
+    let tracked: u128 = state
+        .games
+        .iter()
+        .map(|(_, game)| {
+            game.pot
+                + game.fees.iter().map(|(_, value)| value).sum::<u128>()
+                + game
+                    .series_balances
+                    .iter()
+                    .map(|(_, value)| value)
+                    .sum::<u128>()
+                + game
+                    .pending_withdrawals
+                    .iter()
+                    .map(|(_, value)| value)
+                    .sum::<u128>()
+        })
+        .sum();
+
+    assert_eq!(sys.balance_of(program.id()), tracked + surplus);
+}
+
+#[test]
+fn check_invariant_during_game() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let mistaken_value = 700;
+    check_balance_invariant(&sys, &game, 0);
+
+    for user in COMMON_USERS_SET {
+        check_register_player(&game, *user, COMMON_BET + 1_000);
+        check_balance_invariant(&sys, &game, 0);
+    }
+    assert_eq!(game_state(&game).pot, COMMON_BET * 3);
+
+    let result = game.send_with_value(
+        USERS[0],
+        Action::ChangeNextGameConfig(GAME_ID, COMMON_CONFIG),
+        mistaken_value,
+    );
+    assert!(result.contains(&(
+        USERS[0],
+        Ok::<_, RpsError>(Event::GameConfigChanged(ConfigSlot::Next)).encode()
+    )));
+    check_balance_invariant(&sys, &game, mistaken_value);
+
+    failure_register_player(
+        &game,
+        USERS[1],
+        COMMON_BET,
+        RpsError::PlayerAlreadyRegistered,
+    );
+    check_balance_invariant(&sys, &game, mistaken_value);
+
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    let moves = [Move::Rock, Move::Paper, Move::Rock];
+    for (user, users_move) in COMMON_USERS_SET.iter().zip(moves.iter()) {
+        check_user_move(&game, *user, users_move.clone());
+        check_balance_invariant(&sys, &game, mistaken_value);
+    }
+
+    check_user_reveal_with_continue(&game, USERS[0], Move::Rock);
+    check_user_reveal_with_continue(&game, USERS[1], Move::Paper);
+    check_balance_invariant(&sys, &game, mistaken_value);
+    check_user_reveal_with_game_over(&game, USERS[2], Move::Rock, USERS[1].into());
+    check_balance_invariant(&sys, &game, mistaken_value);
+    assert_eq!(game_state(&game).pot, 0);
+
+    claim_winnings(&sys, &game, USERS[1]);
+    check_balance_invariant(&sys, &game, mistaken_value);
+
+    failure_action(&game, USERS[1], Action::WithdrawSurplus, RpsError::NotOwner);
+    check_action(
+        &game,
+        USERS[0],
+        Action::WithdrawSurplus,
+        Event::SurplusWithdrawn(mistaken_value),
+    );
+    check_balance_invariant(&sys, &game, 0);
+    failure_action(
+        &game,
+        USERS[0],
+        Action::WithdrawSurplus,
+        RpsError::NoSurplus,
+    );
+
+    sys.claim_value_from_mailbox(USERS[0]);
+    check_users_balance(&sys, &USERS[0], START_BALANCE - COMMON_BET);
+}
+
+#[test]
+fn check_invariant_with_indivisible_pot() {
+    let sys = System::new();
+    let bet = COMMON_BET + 1;
+    let game = common_init_with_owner_and_bet(&sys, USERS[0], bet);
+    let other_game_config = GameConfig {
+        bet_size: COMMON_BET * 2,
+        ..COMMON_CONFIG
+    };
+    game.send(USERS[0], Action::CreateGame(other_game_config));
+
+    register_players(&game, COMMON_USERS_SET, bet);
+    check_action_with_value(
+        &game,
+        USERS[3],
+        Action::Register(1),
+        COMMON_BET * 2,
+        Event::PlayerRegistered,
+    );
+    check_balance_invariant(&sys, &game, 0);
+
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    check_user_move(&game, USERS[0], Move::Rock);
+    check_user_move(&game, USERS[1], Move::Paper);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    // the bets of three players are split between two of them, the first one gets the remainder
+    check_stop_the_game(&game, USERS[0], &USERS[0..2]);
+    check_balance_invariant(&sys, &game, 0);
+    let state = game_state(&game);
+    let first = *players(&USERS[0..2]).first().expect("No players");
+    assert_eq!(state.pot, 0);
+    assert!(state
+        .pending_withdrawals
+        .contains(&(first, bet * 3 / 2 + bet * 3 % 2)));
+    assert_eq!(
+        state
+            .pending_withdrawals
+            .iter()
+            .map(|(_, value)| value)
+            .sum::<u128>(),
+        bet * 3
+    );
+
+    failure_action(
+        &game,
+        USERS[0],
+        Action::WithdrawSurplus,
+        RpsError::NoSurplus,
+    );
+}
@@ -21,7 +21,12 @@ fn check_register_twice() {
     let game = common_init(&sys);
 
     check_register_player(&game, USERS[0], COMMON_BET);
-    failure_register_player(&game, USERS[0], COMMON_BET);
+    failure_register_player(
+        &game,
+        USERS[0],
+        COMMON_BET,
+        RpsError::PlayerAlreadyRegistered,
+    );
 }
 
 #[test]
@@ -33,7 +38,12 @@ fn check_register_after_registration_stage_prolongation() {
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
 
-    failure_register_player(&game, USERS[0], COMMON_BET);
+    failure_register_player(
+        &game,
+        USERS[0],
+        COMMON_BET,
+        RpsError::PlayerAlreadyRegistered,
+    );
     check_register_player(&game, USERS[1], COMMON_BET);
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
@@ -52,9 +62,9 @@ fn check_register_in_progress() {
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
 
-    failure_register_player(&game, USERS[0], COMMON_BET);
-    failure_register_player(&game, USERS[1], COMMON_BET);
-    failure_register_player(&game, USERS[2], COMMON_BET);
+    failure_register_player(&game, USERS[0], COMMON_BET, RpsError::GameIsInProgress);
+    failure_register_player(&game, USERS[1], COMMON_BET, RpsError::GameIsInProgress);
+    failure_register_player(&game, USERS[2], COMMON_BET, RpsError::GameIsInProgress);
 }
 
 #[test]
@@ -63,7 +73,7 @@ fn check_register_on_reveal_stage() {
     let game =
         reach_reveal_stage_with_init(&sys, &USERS[0..3], &[Move::Rock, Move::Rock, Move::Rock]);
 
-    failure_register_player(&game, USERS[3], COMMON_BET);
+    failure_register_player(&game, USERS[3], COMMON_BET, RpsError::GameIsInProgress);
 }
 
 #[test]
@@ -74,7 +84,7 @@ fn check_register_after_first_round() {
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
     play_round(&game, &USERS[0..3], &[Move::Rock, Move::Rock, Move::Rock]);
 
-    failure_register_player(&game, USERS[3], COMMON_BET);
+    failure_register_player(&game, USERS[3], COMMON_BET, RpsError::GameIsInProgress);
 }
 
 #[test]
@@ -101,5 +111,58 @@ fn check_register_more_then_possible() {
 
     register_players(&game, &USERS[0..4], COMMON_BET);
     check_register_player(&game, USERS[3] + 1, COMMON_BET);
-    failure_register_player(&game, USERS[3] + 2, COMMON_BET);
+    failure_register_player(&game, USERS[3] + 2, COMMON_BET, RpsError::LobbyIsFull);
+}
+
+#[test]
+fn check_leaving_lobby() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    register_players(&game, &USERS[0..3], COMMON_BET);
+
+    check_leave_lobby(&game, USERS[1]);
+    sys.claim_value_from_mailbox(USERS[1]);
+    check_users_balance(&sys, &USERS[1], START_BALANCE);
+    assert_eq!(game_state(&game).pot, COMMON_BET * 2);
+    failure_leave_lobby(&game, USERS[1], RpsError::PlayerNotInGame);
+
+    // the player can register again while the lobby is open
+    check_register_player(&game, USERS[1], COMMON_BET);
+    check_leave_lobby(&game, USERS[1]);
+    check_leave_lobby(&game, USERS[2]);
+
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    // the game can't start with one player left in the lobby
+    failure_user_move(&game, USERS[0], Move::Rock, RpsError::NotMoveStage);
+}
+
+#[test]
+fn check_leaving_lobby_in_progress() {
+    let sys = System::new();
+    let game = common_init_and_register(&sys);
+
+    failure_leave_lobby(&game, USERS[0], RpsError::GameIsInProgress);
+    failure_leave_lobby(&game, USERS[3], RpsError::GameIsInProgress);
+}
+
+#[test]
+fn check_auto_start_when_lobby_is_full() {
+    let sys = System::new();
+    let game = common_init_with_owner_and_config(
+        &sys,
+        USERS[0],
+        GameConfig {
+            players_count_limit: 3,
+            auto_start: true,
+            ..COMMON_CONFIG
+        },
+    );
+
+    register_players(&game, &USERS[0..2], COMMON_BET);
+    failure_user_move(&game, USERS[0], Move::Rock, RpsError::NotMoveStage);
+
+    check_register_player(&game, USERS[2], COMMON_BET);
+    failure_register_player(&game, USERS[3], COMMON_BET, RpsError::GameIsInProgress);
+    check_user_move(&game, USERS[0], Move::Rock);
 }
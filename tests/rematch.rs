@@ -0,0 +1,193 @@
+use gstd::Encode;
+use gtest::{Program, System};
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+fn init_and_play_game(sys: &System, min_players_to_start: u8) -> Program {
+    let game = common_init_with_owner_and_config(
+        sys,
+        USERS[0],
+        GameConfig {
+            min_players_to_start,
+            rematch_timeout_ms: COMMON_TIMEOUT,
+            ..COMMON_CONFIG
+        },
+    );
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    let result = play_round(
+        &game,
+        COMMON_USERS_SET,
+        &[Move::Rock, Move::Rock, Move::Paper],
+    );
+    assert!(result.contains(&(
+        USERS[2],
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::GameOver {
+            winner: USERS[2].into()
+        }))
+        .encode()
+    )));
+
+    game
+}
+
+#[test]
+fn check_all_players_accept_rematch() {
+    let sys = System::new();
+    let game = init_and_play_game(&sys, 3);
+
+    assert!(matches!(game_state(&game).stage, GameStage::Rematch(_)));
+    failure_register_player(&game, USERS[3], COMMON_BET, RpsError::GameIsInProgress);
+
+    check_action_with_value(
+        &game,
+        USERS[0],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        Event::RematchAccepted(None),
+    );
+    failure_action_with_value(
+        &game,
+        USERS[0],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        RpsError::PlayerAlreadyRegistered,
+    );
+    failure_action_with_value(
+        &game,
+        USERS[3],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        RpsError::PlayerNotInGame,
+    );
+    check_action_with_value(
+        &game,
+        USERS[1],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        Event::RematchAccepted(None),
+    );
+    check_action_with_value(
+        &game,
+        USERS[2],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        Event::RematchAccepted(Some(StageTransition::GameStarted {
+            players: players(COMMON_USERS_SET),
+        })),
+    );
+
+    assert_eq!(game_state(&game).pot, COMMON_BET * 3);
+    check_user_move(&game, USERS[0], Move::Rock);
+}
+
+#[test]
+fn check_rematch_starts_when_enough_players_accept() {
+    let sys = System::new();
+    let game = init_and_play_game(&sys, 2);
+
+    check_action_with_value(
+        &game,
+        USERS[0],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        Event::RematchAccepted(None),
+    );
+    check_action_with_value(
+        &game,
+        USERS[2],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        Event::RematchAccepted(Some(StageTransition::GameStarted {
+            players: players(&[USERS[0], USERS[2]]),
+        })),
+    );
+
+    assert_eq!(game_state(&game).pot, COMMON_BET * 2);
+    check_user_move(&game, USERS[0], Move::Rock);
+    failure_user_move(&game, USERS[1], Move::Rock, RpsError::PlayerNotInGame);
+    failure_action_with_value(
+        &game,
+        USERS[1],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        RpsError::NotRematchStage,
+    );
+}
+
+#[test]
+fn check_rematch_declined() {
+    let sys = System::new();
+    let game = init_and_play_game(&sys, 2);
+
+    check_action_with_value(
+        &game,
+        USERS[0],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        Event::RematchAccepted(None),
+    );
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    // the player who has accepted the rematch stays registered
+    check_register_player(&game, USERS[3], COMMON_BET);
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::Preparation));
+    assert_eq!(state.lobby.len(), 2);
+    assert!(state.lobby.contains(&USERS[0].into()));
+}
+
+#[test]
+fn check_rematch_timeout_validation() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            rematch_timeout_ms: COMMON_TIMEOUT - 1,
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::RematchTimeoutTooLow),
+    );
+}
+
+#[test]
+fn check_forfeited_player_is_not_offered_rematch() {
+    let sys = System::new();
+    let game = common_init_with_owner_and_config(
+        &sys,
+        USERS[0],
+        GameConfig {
+            rematch_timeout_ms: COMMON_TIMEOUT,
+            ..COMMON_CONFIG
+        },
+    );
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    check_user_move(&game, USERS[0], Move::Rock);
+    check_forfeit(&game, USERS[0], None);
+    assert!(!game_state(&game).lobby.contains(&USERS[0].into()));
+
+    check_user_move(&game, USERS[1], Move::Rock);
+    check_user_move(&game, USERS[2], Move::Paper);
+    check_user_reveal_with_continue(&game, USERS[1], Move::Rock);
+    check_user_reveal_with_game_over(&game, USERS[2], Move::Paper, USERS[2].into());
+
+    let GameStage::Rematch(description) = game_state(&game).stage else {
+        panic!("Not rematch stage");
+    };
+    assert_eq!(description.anticipated_players, players(&USERS[1..3]));
+    failure_action_with_value(
+        &game,
+        USERS[0],
+        Action::AcceptRematch(GAME_ID),
+        COMMON_BET,
+        RpsError::PlayerNotInGame,
+    );
+}
@@ -36,7 +36,7 @@ fn check_game_over() {
 
     USERS
         .iter()
-        .for_each(|user| sys.claim_value_from_mailbox(*user));
+        .for_each(|user| claim_winnings(&sys, &game, *user));
 
     check_users_balance(&sys, &USERS[1], 1_000_000_000 + COMMON_BET * 3);
 }
@@ -380,7 +380,7 @@ fn check_reveal_on_preparation_stage() {
     let sys = System::new();
     let game = common_init_and_register(&sys);
 
-    failure_user_reveal(&game, USERS[0], Move::Rock)
+    failure_user_reveal(&game, USERS[0], Move::Rock, RpsError::NotRevealStage)
 }
 
 #[test]
@@ -390,8 +390,8 @@ fn check_reveal_on_progress_stage() {
 
     check_user_move(&game, USERS[0], Move::Rock);
 
-    failure_user_reveal(&game, USERS[0], Move::Rock);
-    failure_user_reveal(&game, USERS[1], Move::Rock);
+    failure_user_reveal(&game, USERS[0], Move::Rock, RpsError::NotRevealStage);
+    failure_user_reveal(&game, USERS[1], Move::Rock, RpsError::NotRevealStage);
 }
 
 #[test]
@@ -403,28 +403,82 @@ fn check_reveal_twice() {
 
     check_user_reveal_with_continue(&game, USERS[0], moves[0].clone());
 
-    failure_user_reveal(&game, USERS[0], moves[0].clone());
-    failure_user_reveal(&game, USERS[0], Move::Rock);
+    failure_user_reveal(
+        &game,
+        USERS[0],
+        moves[0].clone(),
+        RpsError::PlayerAlreadyRevealed,
+    );
+    failure_user_reveal(&game, USERS[0], Move::Rock, RpsError::PlayerAlreadyRevealed);
 }
 
 #[test]
 fn check_third_party_player_reveal() {
     let sys = System::new();
-    let game = common_init_and_register(&sys);
     let moves = [Move::Spock, Move::Lizard, Move::Lizard];
 
-    reach_reveal_stage_with_init(&sys, &USERS[..3], &moves);
+    let game = reach_reveal_stage_with_init(&sys, &USERS[..3], &moves);
 
-    failure_user_reveal(&game, USERS[3], Move::Rock);
+    failure_user_reveal(&game, USERS[3], Move::Rock, RpsError::PlayerNotInGame);
 }
 
 #[test]
 fn check_other_password_reveal() {
     let sys = System::new();
-    let game = common_init_and_register(&sys);
     let moves = [Move::Spock, Move::Lizard, Move::Lizard, Move::Lizard];
 
-    reach_reveal_stage_with_init(&sys, USERS, &moves);
+    let game = reach_reveal_stage_with_init(&sys, USERS, &moves);
+
+    failure_user_reveal_with_password(&game, USERS[3], Move::Rock, "pass", RpsError::MoveMismatch);
+}
+
+#[test]
+fn check_unknown_move_reveal() {
+    let sys = System::new();
+    let game = common_init_and_register(&sys);
+    let unknown_move = b"7pass12".to_vec();
+
+    let hash_bytes = commitment(&game, USERS[0], &unknown_move);
+    let result = game.send(USERS[0], Action::MakeMove(GAME_ID, hash_bytes.to_vec()));
+    assert!(result.contains(&(
+        USERS[0],
+        Ok::<_, RpsError>(Event::SuccessfulMove(USERS[0].into())).encode()
+    )));
+    check_user_move(&game, USERS[1], Move::Rock);
+    check_user_move(&game, USERS[2], Move::Rock);
+
+    let result = game.send(USERS[0], Action::Reveal(GAME_ID, unknown_move));
+    assert!(result.contains(&(USERS[0], Err::<Event, _>(RpsError::UnknownMove).encode())));
+}
+
+#[test]
+fn check_commitment_from_previous_round() {
+    let sys = System::new();
+    let users = &USERS[0..2];
+    let moves = [Move::Rock, Move::Rock];
+
+    let game = reach_reveal_stage_with_init(&sys, users, &moves);
+
+    let real_move = Move::Rock.number().to_string() + DEFAULT_PASSWORD;
+    let previous_commitment = commitment(&game, users[0], real_move.as_bytes());
+
+    check_user_reveal_with_continue(&game, users[0], moves[0].clone());
+
+    let next_round_players: BTreeSet<ActorId> = users.iter().copied().map(|id| id.into()).collect();
+
+    check_user_reveal_with_next_round(&game, users[1], moves[1].clone(), next_round_players);
+
+    check_user_move(&game, users[0], Move::Paper);
+
+    let result = game.send(
+        users[1],
+        Action::MakeMove(GAME_ID, previous_commitment.to_vec()),
+    );
+    assert!(result.contains(&(
+        users[1],
+        Ok::<_, RpsError>(Event::SuccessfulMove(users[1].into())).encode()
+    )));
 
-    failure_user_reveal_with_password(&game, USERS[3], Move::Rock, "pass");
+    check_user_reveal_with_continue(&game, users[0], Move::Paper);
+    failure_user_reveal(&game, users[1], Move::Rock, RpsError::MoveMismatch);
 }
@@ -0,0 +1,65 @@
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+#[test]
+fn check_role_permissions() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let (admin, operator) = (USERS[1], USERS[2]);
+
+    failure_action(
+        &game,
+        admin,
+        Action::GrantRole(admin.into(), Role::Admin),
+        RpsError::NotOwner,
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::GrantRole(admin.into(), Role::Admin),
+        Event::RoleGranted(admin.into(), Role::Admin),
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::GrantRole(operator.into(), Role::Operator),
+        Event::RoleGranted(operator.into(), Role::Operator),
+    );
+
+    let state = contract_state(&game);
+    assert_eq!(state.roles.len(), 2);
+    assert!(state.roles.contains(&(operator.into(), Role::Operator)));
+
+    // operators can stop the games but can't configure them
+    failure_change_next_game_config(&game, operator, COMMON_CONFIG, RpsError::NotAdmin);
+    check_stop_the_game(&game, operator, &[]);
+
+    check_change_next_game_config(&game, admin, COMMON_CONFIG);
+    check_stop_the_game(&game, admin, &[]);
+
+    // only the owner manages the roles and the value
+    failure_action(
+        &game,
+        admin,
+        Action::RevokeRole(operator.into()),
+        RpsError::NotOwner,
+    );
+    failure_action(&game, admin, Action::WithdrawSurplus, RpsError::NotOwner);
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::RevokeRole(operator.into()),
+        Event::RoleRevoked(operator.into()),
+    );
+    failure_stop_the_game(&game, operator, RpsError::NotOperator);
+    failure_action(
+        &game,
+        USERS[0],
+        Action::RevokeRole(operator.into()),
+        RpsError::NoRole,
+    );
+}
@@ -1,4 +1,4 @@
-use gstd::{prelude::*, ActorId, Encode};
+use gstd::{prelude::*, ActorId, Decode, Encode};
 use gtest::{Program, RunResult, System};
 use rps_io::*;
 
@@ -9,12 +9,24 @@ pub const COMMON_BET: u128 = 1_000_000;
 pub const START_BALANCE: u128 = 1_000_000_000;
 pub const COMMON_PLAYERS_COUNT_LIMIT: u8 = 5;
 pub const COMMON_TIMEOUT: u64 = 5_000;
+pub const GAME_ID: GameId = INIT_GAME_ID;
 pub const COMMON_CONFIG: GameConfig = GameConfig {
     bet_size: COMMON_BET,
     players_count_limit: COMMON_PLAYERS_COUNT_LIMIT,
+    allowlist: None,
+    min_players_to_start: 2,
+    auto_start: false,
     entry_timeout_ms: COMMON_TIMEOUT,
     move_timeout_ms: COMMON_TIMEOUT,
     reveal_timeout_ms: COMMON_TIMEOUT,
+    rematch_timeout_ms: 0,
+    block_timeouts: None,
+    block_time_ms: 1_000,
+    registration_opens_at: None,
+    starts_at: None,
+    abandon_policy: AbandonPolicy::Prolong,
+    fee_bps: 0,
+    treasury: ActorId::zero(),
 };
 
 pub trait NumberConvertable {
@@ -37,27 +49,67 @@ pub fn blocks_count(timout: u64) -> u32 {
     timout as _
 }
 
+const STATE_WASM: &str = if cfg!(debug_assertions) {
+    "target/wasm32-unknown-unknown/debug/rps_state.meta.wasm"
+} else {
+    "target/wasm32-unknown-unknown/release/rps_state.meta.wasm"
+};
+
+/// Reads the state with the `rps-state` function, the arguments before the state are passed as a tuple.
+pub fn meta_state<E: Encode, D: Decode>(
+    program: &Program,
+    fn_name: &str,
+    argument: Option<E>,
+) -> D {
+    let wasm = std::fs::read(STATE_WASM).expect("Can't read the state wasm");
+
+    program
+        .read_state_using_wasm(fn_name, wasm, argument)
+        .expect("Not suitable reply")
+}
+
+pub fn contract_state(program: &Program) -> ContractState {
+    program.read_state().expect("Not suitable reply")
+}
+
+pub fn game_state(program: &Program) -> GameState {
+    contract_state(program)
+        .game(GAME_ID)
+        .expect("Game not found")
+        .clone()
+}
+
+pub fn players(users: &[u64]) -> BTreeSet<ActorId> {
+    users.iter().copied().map(Into::into).collect()
+}
+
 pub fn common_init(sys: &System) -> Program {
     common_init_with_owner_and_bet(sys, USERS[0], COMMON_BET)
 }
 
 pub fn common_init_with_owner_and_bet(sys: &System, owner_user: u64, bet_size: u128) -> Program {
+    common_init_with_owner_and_config(
+        sys,
+        owner_user,
+        GameConfig {
+            bet_size,
+            ..COMMON_CONFIG
+        },
+    )
+}
+
+pub fn common_init_with_owner_and_config(
+    sys: &System,
+    owner_user: u64,
+    config: GameConfig,
+) -> Program {
     sys.init_logger();
     USERS
         .iter()
         .copied()
         .for_each(|id| sys.mint_to(id, START_BALANCE));
     let program = Program::current(sys);
-    let result = program.send(
-        owner_user,
-        GameConfig {
-            bet_size,
-            players_count_limit: COMMON_PLAYERS_COUNT_LIMIT,
-            entry_timeout_ms: COMMON_TIMEOUT,
-            move_timeout_ms: COMMON_TIMEOUT,
-            reveal_timeout_ms: COMMON_TIMEOUT,
-        },
-    );
+    let result = program.send(owner_user, config);
 
     assert!(!result.main_failed());
 
@@ -127,21 +179,56 @@ pub fn play_round(game: &Program, users: &[u64], moves: &[Move]) -> RunResult {
 }
 
 pub fn check_user_move(program: &Program, player: u64, users_move: Move) {
-    let result = try_to_move(program, player, users_move);
+    check_user_move_in_game(program, GAME_ID, player, users_move);
+}
+
+pub fn check_user_move_in_game(program: &Program, game_id: GameId, player: u64, users_move: Move) {
+    let result = try_to_move_in_game(program, game_id, player, users_move);
 
-    assert!(result.contains(&(player, Event::SuccessfulMove(player.into()).encode())));
+    assert!(result.contains(&(
+        player,
+        Ok::<_, RpsError>(Event::SuccessfulMove(player.into())).encode()
+    )));
 }
 
-pub fn failure_user_move(program: &Program, player: u64, users_move: Move) {
+pub fn failure_user_move(program: &Program, player: u64, users_move: Move, error: RpsError) {
     let result = try_to_move(program, player, users_move);
 
-    assert!(result.main_failed());
+    assert!(result.contains(&(player, Err::<Event, _>(error).encode())));
 }
 
 pub fn try_to_move(program: &Program, player: u64, users_move: Move) -> RunResult {
+    try_to_move_in_game(program, GAME_ID, player, users_move)
+}
+
+fn try_to_move_in_game(
+    program: &Program,
+    game_id: GameId,
+    player: u64,
+    users_move: Move,
+) -> RunResult {
     let move_with_pass = users_move.number().to_string() + DEFAULT_PASSWORD;
-    let hash_bytes = sp_core_hashing::blake2_256(move_with_pass.as_bytes());
-    program.send(player, Action::MakeMove(hash_bytes.to_vec()))
+    let hash_bytes = commitment_in_game(program, game_id, player, move_with_pass.as_bytes());
+    program.send(player, Action::MakeMove(game_id, hash_bytes.to_vec()))
+}
+
+pub fn commitment(program: &Program, player: u64, real_move: &[u8]) -> [u8; 32] {
+    commitment_in_game(program, GAME_ID, player, real_move)
+}
+
+fn commitment_in_game(
+    program: &Program,
+    game_id: GameId,
+    player: u64,
+    real_move: &[u8],
+) -> [u8; 32] {
+    let round = contract_state(program)
+        .game(game_id)
+        .expect("Game not found")
+        .round;
+    let preimage = commitment_preimage(real_move, &player.into(), game_id, round);
+
+    sp_core_hashing::blake2_256(&preimage)
 }
 
 pub fn check_user_reveal_with_continue(program: &Program, player: u64, users_move: Move) {
@@ -149,7 +236,7 @@ pub fn check_user_reveal_with_continue(program: &Program, player: u64, users_mov
 
     assert!(result.contains(&(
         player,
-        Event::SuccessfulReveal(RevealResult::Continue).encode()
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::Continue)).encode()
     )));
 }
 
@@ -163,9 +250,9 @@ pub fn check_user_reveal_with_next_round(
 
     assert!(result.contains(&(
         player,
-        Event::SuccessfulReveal(RevealResult::NextRoundStarted {
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::NextRoundStarted {
             players: next_round_players
-        })
+        }))
         .encode()
     )));
 }
@@ -180,14 +267,14 @@ pub fn check_user_reveal_with_game_over(
 
     assert!(result.contains(&(
         player,
-        Event::SuccessfulReveal(RevealResult::GameOver { winner }).encode()
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::GameOver { winner })).encode()
     )));
 }
 
-pub fn failure_user_reveal(program: &Program, player: u64, users_move: Move) {
+pub fn failure_user_reveal(program: &Program, player: u64, users_move: Move, error: RpsError) {
     let result = try_to_reveal(program, player, users_move);
 
-    assert!(result.main_failed());
+    assert!(result.contains(&(player, Err::<Event, _>(error).encode())));
 }
 
 pub fn failure_user_reveal_with_password(
@@ -195,61 +282,183 @@ pub fn failure_user_reveal_with_password(
     player: u64,
     users_move: Move,
     password: &str,
+    error: RpsError,
 ) {
-    let result = try_to_reveal_with_password(program, player, users_move, password);
+    let result = try_to_reveal_with_password(program, GAME_ID, player, users_move, password);
 
-    assert!(result.main_failed());
+    assert!(result.contains(&(player, Err::<Event, _>(error).encode())));
 }
 
 fn try_to_reveal(program: &Program, player: u64, users_move: Move) -> RunResult {
-    try_to_reveal_with_password(program, player, users_move, DEFAULT_PASSWORD)
+    try_to_reveal_in_game(program, GAME_ID, player, users_move)
+}
+
+pub fn try_to_reveal_in_game(
+    program: &Program,
+    game_id: GameId,
+    player: u64,
+    users_move: Move,
+) -> RunResult {
+    try_to_reveal_with_password(program, game_id, player, users_move, DEFAULT_PASSWORD)
 }
 
 fn try_to_reveal_with_password(
     program: &Program,
+    game_id: GameId,
     player: u64,
     users_move: Move,
     password: &str,
 ) -> RunResult {
     let move_with_pass = users_move.number().to_string() + password;
 
-    program.send(player, Action::Reveal(move_with_pass.as_bytes().to_vec()))
+    program.send(
+        player,
+        Action::Reveal(game_id, move_with_pass.as_bytes().to_vec()),
+    )
+}
+
+/// Sends the action and checks that it has succeeded with the event.
+pub fn check_action(program: &Program, from: u64, action: Action, event: Event) {
+    let result = program.send(from, action);
+
+    assert!(result.contains(&(from, Ok::<_, RpsError>(event).encode())));
+}
+
+/// Sends the action with the value and checks that it has succeeded with the event.
+pub fn check_action_with_value(
+    program: &Program,
+    from: u64,
+    action: Action,
+    value: u128,
+    event: Event,
+) {
+    let result = program.send_with_value(from, action, value);
+
+    assert!(result.contains(&(from, Ok::<_, RpsError>(event).encode())));
+}
+
+/// Sends the action and checks that it has failed with the error.
+pub fn failure_action(program: &Program, from: u64, action: Action, error: RpsError) {
+    let result = program.send(from, action);
+
+    assert!(result.contains(&(from, Err::<Event, _>(error).encode())));
+}
+
+/// Sends the action with the value and checks that it has failed with the error.
+pub fn failure_action_with_value(
+    program: &Program,
+    from: u64,
+    action: Action,
+    value: u128,
+    error: RpsError,
+) {
+    let result = program.send_with_value(from, action, value);
+
+    assert!(result.contains(&(from, Err::<Event, _>(error).encode())));
 }
 
 pub fn check_register_player(program: &Program, from: u64, bet: u128) {
-    let result = program.send_with_value(from, Action::Register, bet);
+    let result = program.send_with_value(from, Action::Register(GAME_ID), bet);
+
+    assert!(result.contains(&(from, Ok::<_, RpsError>(Event::PlayerRegistered).encode())));
+}
+
+pub fn failure_register_player(program: &Program, from: u64, bet: u128, error: RpsError) {
+    let result = program.send_with_value(from, Action::Register(GAME_ID), bet);
+
+    assert!(result.contains(&(from, Err::<Event, _>(error).encode())));
+}
+
+pub fn check_leave_lobby(program: &Program, from: u64) {
+    let result = program.send(from, Action::LeaveLobby(GAME_ID));
 
-    assert!(result.contains(&(from, Event::PlayerRegistered.encode())));
+    assert!(result.contains(&(from, Ok::<_, RpsError>(Event::PlayerLeft).encode())));
 }
 
-pub fn failure_register_player(program: &Program, from: u64, bet: u128) {
-    let result = program.send_with_value(from, Action::Register, bet);
+pub fn failure_leave_lobby(program: &Program, from: u64, error: RpsError) {
+    let result = program.send(from, Action::LeaveLobby(GAME_ID));
 
-    assert!(result.main_failed());
+    assert!(result.contains(&(from, Err::<Event, _>(error).encode())));
 }
 
 pub fn check_change_next_game_config(program: &Program, from: u64, config: GameConfig) {
-    let result = program.send(from, Action::ChangeNextGameConfig(config));
+    let result = program.send(from, Action::ChangeNextGameConfig(GAME_ID, config));
+
+    assert!(result.contains(&(
+        from,
+        Ok::<_, RpsError>(Event::GameConfigChanged(ConfigSlot::Next)).encode()
+    )));
+}
 
-    assert!(result.contains(&(from, Event::GameConfigChanged.encode())));
+pub fn check_change_current_game_config(program: &Program, from: u64, config: GameConfig) {
+    let result = program.send(from, Action::ChangeNextGameConfig(GAME_ID, config));
+
+    assert!(result.contains(&(
+        from,
+        Ok::<_, RpsError>(Event::GameConfigChanged(ConfigSlot::Current)).encode()
+    )));
+}
+
+pub fn failure_change_next_game_config(
+    program: &Program,
+    from: u64,
+    config: GameConfig,
+    error: RpsError,
+) {
+    let result = program.send(from, Action::ChangeNextGameConfig(GAME_ID, config));
+
+    assert!(result.contains(&(from, Err::<Event, _>(error).encode())));
+}
+
+pub fn check_forfeit(program: &Program, from: u64, transition: Option<StageTransition>) {
+    let result = program.send(from, Action::Forfeit(GAME_ID));
+
+    assert!(result.contains(&(
+        from,
+        Ok::<_, RpsError>(Event::PlayerForfeited(transition)).encode()
+    )));
 }
 
-pub fn failure_change_next_game_config(program: &Program, from: u64, config: GameConfig) {
-    let result = program.send(from, Action::ChangeNextGameConfig(config));
+pub fn failure_forfeit(program: &Program, from: u64, error: RpsError) {
+    let result = program.send(from, Action::Forfeit(GAME_ID));
 
-    assert!(result.main_failed());
+    assert!(result.contains(&(from, Err::<Event, _>(error).encode())));
 }
 
 pub fn check_stop_the_game(program: &Program, from: u64, rewarded_users: &[u64]) {
-    let result = program.send(from, Action::StopGame);
+    let result = program.send(from, Action::StopGame(GAME_ID));
     let rewarded_users = rewarded_users.iter().cloned().map(Into::into).collect();
-    assert!(result.contains(&(from, Event::GameStopped(rewarded_users).encode())));
+    assert!(result.contains(&(
+        from,
+        Ok::<_, RpsError>(Event::GameStopped(rewarded_users)).encode()
+    )));
+}
+
+pub fn failure_stop_the_game(program: &Program, from: u64, error: RpsError) {
+    let result = program.send(from, Action::StopGame(GAME_ID));
+
+    assert!(result.contains(&(from, Err::<Event, _>(error).encode())));
+}
+
+pub fn check_advance_stage(program: &Program, from: u64, transition: StageTransition) {
+    let result = program.send(from, Action::AdvanceStage(GAME_ID));
+
+    assert!(result.contains(&(
+        from,
+        Ok::<_, RpsError>(Event::StageAdvanced(transition)).encode()
+    )));
 }
 
-pub fn failure_stop_the_game(program: &Program, from: u64) {
-    let result = program.send(from, Action::StopGame);
+pub fn failure_advance_stage(program: &Program, from: u64, error: RpsError) {
+    let result = program.send(from, Action::AdvanceStage(GAME_ID));
+
+    assert!(result.contains(&(from, Err::<Event, _>(error).encode())));
+}
 
-    assert!(result.main_failed());
+/// Claims the pending withdrawals of the user in the game if there are any.
+pub fn claim_winnings(sys: &System, program: &Program, user: u64) {
+    program.send(user, Action::Claim(GAME_ID));
+    sys.claim_value_from_mailbox(user);
 }
 
 pub fn check_users_balance(sys: &System, user: &u64, balance: u128) {
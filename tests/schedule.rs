@@ -0,0 +1,80 @@
+use gtest::System;
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+#[test]
+fn check_scheduled_game() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let init_timestamp = game_state(&game).current_stage_start_timestamp;
+    let registration_opens_at = init_timestamp + COMMON_TIMEOUT * 2;
+    let starts_at = init_timestamp + COMMON_TIMEOUT * 6;
+
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            registration_opens_at: Some(registration_opens_at),
+            starts_at: Some(starts_at),
+            ..COMMON_CONFIG
+        },
+    );
+
+    failure_register_player(&game, USERS[0], COMMON_BET, RpsError::RegistrationNotOpen);
+
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT * 2 / 1_000));
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    // the registration lasts longer than `entry_timeout_ms` until `starts_at`
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT * 2 / 1_000));
+    failure_user_move(&game, USERS[0], Move::Rock, RpsError::NotMoveStage);
+    failure_advance_stage(&game, USERS[3], RpsError::DeadlineNotReached);
+
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    check_user_move(&game, USERS[0], Move::Rock);
+    assert!(game_state(&game).current_stage_start_timestamp > starts_at);
+}
+
+#[test]
+fn check_schedule_validation() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    failure_change_next_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            registration_opens_at: Some(COMMON_TIMEOUT),
+            starts_at: Some(COMMON_TIMEOUT),
+            ..COMMON_CONFIG
+        },
+        RpsError::InvalidConfig(ConfigError::StartBeforeRegistrationOpens),
+    );
+}
+
+#[test]
+fn check_far_start_without_interaction() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let init_timestamp = game_state(&game).current_stage_start_timestamp;
+    // `starts_at` is much later than the longest stage timeout of the config
+    let starts_at = init_timestamp + COMMON_TIMEOUT * 20;
+
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            starts_at: Some(starts_at),
+            ..COMMON_CONFIG
+        },
+    );
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT * 10 / 1_000));
+    assert!(matches!(game_state(&game).stage, GameStage::Preparation));
+
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT * 10 / 1_000 + 2));
+    assert!(matches!(game_state(&game).stage, GameStage::InProgress(_)));
+}
@@ -0,0 +1,202 @@
+use gstd::Encode;
+use gtest::{Program, System};
+use rps_io::*;
+
+mod routines;
+pub use routines::*;
+
+fn play_game_without_series_players(sys: &System, game: &Program) {
+    register_players(game, &USERS[2..4], COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    play_round(game, &USERS[2..4], &[Move::Rock, Move::Paper]);
+}
+
+#[test]
+fn check_series_games() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::SetSeries(GAME_ID, Some(SeriesLength::Games(2))),
+        Event::SeriesSet,
+    );
+    check_action_with_value(
+        &game,
+        USERS[0],
+        Action::JoinSeries(GAME_ID),
+        COMMON_BET,
+        Event::JoinedSeries(COMMON_BET),
+    );
+    check_action_with_value(
+        &game,
+        USERS[0],
+        Action::JoinSeries(GAME_ID),
+        COMMON_BET,
+        Event::JoinedSeries(COMMON_BET * 2),
+    );
+    check_action_with_value(
+        &game,
+        USERS[1],
+        Action::JoinSeries(GAME_ID),
+        COMMON_BET * 2,
+        Event::JoinedSeries(COMMON_BET * 2),
+    );
+
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    play_round(
+        &game,
+        COMMON_USERS_SET,
+        &[Move::Rock, Move::Rock, Move::Paper],
+    );
+
+    // the players of the series are registered and the next game starts at once
+    let state = game_state(&game);
+    assert_eq!(state.stage.current_players(), Some(players(&USERS[0..2])));
+    assert_eq!(state.pot, COMMON_BET * 2);
+    assert_eq!(state.series, Some(SeriesLength::Games(1)));
+
+    let result = play_round(&game, &USERS[0..2], &[Move::Rock, Move::Paper]);
+    assert!(result.contains(&(
+        USERS[1],
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::GameOver {
+            winner: USERS[1].into()
+        }))
+        .encode()
+    )));
+
+    // the series is over
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::Preparation));
+    assert!(state.lobby.is_empty());
+    assert_eq!(state.series, None);
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::LeaveSeries(GAME_ID),
+        Event::LeftSeries(COMMON_BET),
+    );
+    sys.claim_value_from_mailbox(USERS[0]);
+    check_users_balance(&sys, &USERS[0], START_BALANCE - COMMON_BET * 2);
+}
+
+#[test]
+fn check_series_without_enough_players() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::SetSeries(GAME_ID, Some(SeriesLength::Unlimited)),
+        Event::SeriesSet,
+    );
+    check_action_with_value(
+        &game,
+        USERS[0],
+        Action::JoinSeries(GAME_ID),
+        COMMON_BET,
+        Event::JoinedSeries(COMMON_BET),
+    );
+    check_action_with_value(
+        &game,
+        USERS[1],
+        Action::JoinSeries(GAME_ID),
+        COMMON_BET / 2,
+        Event::JoinedSeries(COMMON_BET / 2),
+    );
+
+    play_game_without_series_players(&sys, &game);
+
+    // the balance of the second player is less than the bet, so the lobby stays open
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::Preparation));
+    assert_eq!(state.lobby, vec![USERS[0].into()]);
+    assert_eq!(state.series, Some(SeriesLength::Unlimited));
+
+    check_register_player(&game, USERS[2], COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    check_user_move(&game, USERS[0], Move::Rock);
+}
+
+#[test]
+fn check_series_failures() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    failure_action(
+        &game,
+        USERS[1],
+        Action::JoinSeries(GAME_ID),
+        RpsError::SeriesNotActive,
+    );
+    failure_action(
+        &game,
+        USERS[1],
+        Action::SetSeries(GAME_ID, Some(SeriesLength::Unlimited)),
+        RpsError::NotAdmin,
+    );
+    failure_action(
+        &game,
+        USERS[1],
+        Action::LeaveSeries(GAME_ID),
+        RpsError::NotInSeries,
+    );
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::SetSeries(GAME_ID, Some(SeriesLength::Unlimited)),
+        Event::SeriesSet,
+    );
+    failure_action(
+        &game,
+        USERS[1],
+        Action::JoinSeries(GAME_ID),
+        RpsError::ZeroValue,
+    );
+}
+
+#[test]
+fn check_stopped_game_is_not_counted() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::SetSeries(GAME_ID, Some(SeriesLength::Games(2))),
+        Event::SeriesSet,
+    );
+    check_action_with_value(
+        &game,
+        USERS[0],
+        Action::JoinSeries(GAME_ID),
+        COMMON_BET,
+        Event::JoinedSeries(COMMON_BET),
+    );
+    check_action_with_value(
+        &game,
+        USERS[1],
+        Action::JoinSeries(GAME_ID),
+        COMMON_BET,
+        Event::JoinedSeries(COMMON_BET),
+    );
+
+    register_players(&game, &USERS[2..4], COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    check_stop_the_game(&game, USERS[0], &USERS[2..4]);
+
+    // the players of the series are not charged and the registration is open
+    let state = game_state(&game);
+    assert!(matches!(state.stage, GameStage::Preparation));
+    assert!(state.lobby.is_empty());
+    assert_eq!(state.series, Some(SeriesLength::Games(2)));
+    assert!(state
+        .series_balances
+        .iter()
+        .all(|(_, balance)| *balance == COMMON_BET));
+}
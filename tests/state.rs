@@ -27,10 +27,11 @@ fn common_config_tests() {
         entry_timeout_ms: COMMON_TIMEOUT * 2,
         move_timeout_ms: COMMON_TIMEOUT * 3,
         reveal_timeout_ms: COMMON_TIMEOUT * 4,
+        ..COMMON_CONFIG
     };
     check_change_next_game_config(&game, USERS[0], next_config.clone());
 
-    let state: ContractState = game.read_state().expect("Not suitable reply");
+    let state = game_state(&game);
     assert_eq!(COMMON_CONFIG, state.game_config);
 
     play_round(
@@ -39,7 +40,7 @@ fn common_config_tests() {
         &[Move::Rock, Move::Paper, Move::Rock],
     );
 
-    let state: ContractState = game.read_state().expect("Not suitable reply");
+    let state = game_state(&game);
     assert_eq!(next_config, state.game_config);
 }
 
@@ -48,16 +49,16 @@ fn common_stage_tests() {
     let sys = System::new();
     let game = common_init_and_register(&sys);
 
-    let state: ContractState = game.read_state().expect("Not suitable reply");
+    let state = game_state(&game);
     let stage = state.stage;
     match stage {
         GameStage::Preparation => {}
-        GameStage::Reveal(_) | GameStage::InProgress(_) => panic!("wrong"),
+        GameStage::Reveal(_) | GameStage::InProgress(_) | GameStage::Rematch(_) => panic!("wrong"),
     }
 
     check_user_move(&game, USERS[0], Move::Rock);
 
-    let state: ContractState = game.read_state().expect("Not suitable reply");
+    let state = game_state(&game);
 
     let list = COMMON_USERS_SET
         .iter()
@@ -68,7 +69,7 @@ fn common_stage_tests() {
     lobby.sort();
     assert_eq!(lobby, list);
 
-    let state: ContractState = game.read_state().expect("Not suitable reply");
+    let state = game_state(&game);
     let stage = state.stage;
     match stage {
         GameStage::InProgress(description) => {
@@ -81,7 +82,7 @@ fn common_stage_tests() {
             assert_eq!(description.anticipated_players, anticipated);
             assert_eq!(description.finished_players, BTreeSet::from([done]));
         }
-        GameStage::Reveal(_) | GameStage::Preparation => panic!("wrong"),
+        GameStage::Reveal(_) | GameStage::Preparation | GameStage::Rematch(_) => panic!("wrong"),
     }
 }
 
@@ -90,7 +91,7 @@ fn lobby_list_test() {
     let sys = System::new();
     let game = common_init_and_register(&sys);
 
-    let state: ContractState = game.read_state().expect("Not suiable reply");
+    let state = game_state(&game);
 
     let list = COMMON_USERS_SET
         .iter()
@@ -107,7 +108,7 @@ fn lobby_list_test() {
         &[Move::Rock, Move::Paper, Move::Paper],
     );
 
-    let state: ContractState = game.read_state().expect("Not suiable reply");
+    let state = game_state(&game);
 
     let list = COMMON_USERS_SET
         .iter()
@@ -118,3 +119,192 @@ fn lobby_list_test() {
     lobby.sort();
     assert_eq!(lobby, list);
 }
+
+#[test]
+fn role_holders_test() {
+    let sys = System::new();
+    let game = common_init(&sys);
+
+    check_action(
+        &game,
+        USERS[0],
+        Action::GrantRole(USERS[1].into(), Role::Admin),
+        Event::RoleGranted(USERS[1].into(), Role::Admin),
+    );
+    check_action(
+        &game,
+        USERS[0],
+        Action::GrantRole(USERS[2].into(), Role::Operator),
+        Event::RoleGranted(USERS[2].into(), Role::Operator),
+    );
+
+    let admins: Vec<ActorId> = meta_state(&game, "role_holders", Some(Role::Admin));
+    let operators: Vec<ActorId> = meta_state(&game, "role_holders", Some(Role::Operator));
+    assert_eq!(admins, vec![USERS[1].into()]);
+    assert_eq!(operators, vec![USERS[2].into()]);
+}
+
+#[test]
+fn open_games_test() {
+    let sys = System::new();
+    let game = common_init_and_register(&sys);
+    check_action(
+        &game,
+        USERS[0],
+        Action::CreateGame(COMMON_CONFIG),
+        Event::GameCreated(1),
+    );
+
+    let mut open_games: Vec<GameId> = meta_state(&game, "open_games", None::<()>);
+    open_games.sort();
+    assert_eq!(open_games, vec![GAME_ID, 1]);
+
+    // the first game starts
+    check_user_move(&game, USERS[0], Move::Rock);
+
+    let open_games: Vec<GameId> = meta_state(&game, "open_games", None::<()>);
+    assert_eq!(open_games, vec![1]);
+}
+
+#[test]
+fn claimable_and_fees_test() {
+    let sys = System::new();
+    let treasury = 10;
+    let fee_bps = 1_000;
+    let game = common_init_with_owner_and_config(
+        &sys,
+        USERS[0],
+        GameConfig {
+            fee_bps,
+            treasury: treasury.into(),
+            ..COMMON_CONFIG
+        },
+    );
+    register_players(&game, COMMON_USERS_SET, COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    play_round(
+        &game,
+        COMMON_USERS_SET,
+        &[Move::Rock, Move::Paper, Move::Rock],
+    );
+
+    let pot = COMMON_BET * 3;
+    let fee = pot * fee_bps as u128 / 10_000;
+    let claimable: Vec<(GameId, u128)> =
+        meta_state(&game, "claimable", Some(ActorId::from(USERS[1])));
+    let nothing: Vec<(GameId, u128)> =
+        meta_state(&game, "claimable", Some(ActorId::from(USERS[0])));
+    let fees: Vec<(ActorId, u128)> = meta_state(&game, "fees", Some(GAME_ID));
+    assert_eq!(claimable, vec![(GAME_ID, pot - fee)]);
+    assert!(nothing.is_empty());
+    assert_eq!(fees, vec![(treasury.into(), fee)]);
+}
+
+#[test]
+fn series_balance_test() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    check_action(
+        &game,
+        USERS[0],
+        Action::SetSeries(GAME_ID, Some(SeriesLength::Unlimited)),
+        Event::SeriesSet,
+    );
+    let result = game.send_with_value(USERS[1], Action::JoinSeries(GAME_ID), COMMON_BET);
+    assert!(!result.main_failed());
+
+    let balance: Option<u128> = meta_state(
+        &game,
+        "series_balance",
+        Some((GAME_ID, ActorId::from(USERS[1]))),
+    );
+    let no_balance: Option<u128> = meta_state(
+        &game,
+        "series_balance",
+        Some((GAME_ID, ActorId::from(USERS[2]))),
+    );
+    assert_eq!(balance, Some(COMMON_BET));
+    assert_eq!(no_balance, None);
+}
+
+#[test]
+fn is_blocked_test() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let expires_at = game_state(&game).current_stage_start_timestamp + COMMON_TIMEOUT;
+    check_action(
+        &game,
+        USERS[0],
+        Action::BlockPlayer(USERS[1].into(), Some(expires_at)),
+        Event::PlayerBlocked,
+    );
+
+    let player = ActorId::from(USERS[1]);
+    let blocked: bool = meta_state(&game, "is_blocked", Some((player, expires_at - 1)));
+    let expired: bool = meta_state(&game, "is_blocked", Some((player, expires_at)));
+    let other: bool = meta_state(
+        &game,
+        "is_blocked",
+        Some((ActorId::from(USERS[2]), expires_at - 1)),
+    );
+    assert!(blocked);
+    assert!(!expired);
+    assert!(!other);
+}
+
+#[test]
+fn stage_timing_test() {
+    let sys = System::new();
+    let game = common_init(&sys);
+    let registration_opens_at = game_state(&game).current_stage_start_timestamp + COMMON_TIMEOUT;
+
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            registration_opens_at: Some(registration_opens_at),
+            ..COMMON_CONFIG
+        },
+    );
+
+    let start_timestamp = game_state(&game).current_stage_start_timestamp;
+    let start_block: Option<u32> = meta_state(&game, "current_stage_start_block", Some(GAME_ID));
+    let deadline: Option<Deadline> = meta_state(&game, "current_stage_deadline", Some(GAME_ID));
+    let end_timestamp: Option<u64> =
+        meta_state(&game, "current_stage_end_timestamp", Some(GAME_ID));
+    let opens_at: Option<u64> = meta_state(&game, "registration_opens_at", Some(GAME_ID));
+    assert_eq!(
+        start_block,
+        Some(game_state(&game).current_stage_start_block)
+    );
+    assert_eq!(
+        deadline,
+        Some(Deadline::Timestamp(start_timestamp + COMMON_TIMEOUT))
+    );
+    assert_eq!(end_timestamp, Some(start_timestamp + COMMON_TIMEOUT));
+    assert_eq!(opens_at, Some(registration_opens_at));
+
+    // the block deadlines have no end timestamp
+    check_change_current_game_config(
+        &game,
+        USERS[0],
+        GameConfig {
+            block_timeouts: Some(BlockTimeouts {
+                entry_timeout_blocks: 10,
+                move_timeout_blocks: 10,
+                reveal_timeout_blocks: 10,
+                rematch_timeout_blocks: 0,
+            }),
+            ..COMMON_CONFIG
+        },
+    );
+
+    let start_block = game_state(&game).current_stage_start_block;
+    let deadline: Option<Deadline> = meta_state(&game, "current_stage_deadline", Some(GAME_ID));
+    let end_timestamp: Option<u64> =
+        meta_state(&game, "current_stage_end_timestamp", Some(GAME_ID));
+    let opens_at: Option<u64> = meta_state(&game, "registration_opens_at", Some(GAME_ID));
+    assert_eq!(deadline, Some(Deadline::BlockHeight(start_block + 10)));
+    assert_eq!(end_timestamp, None);
+    assert_eq!(opens_at, None);
+}
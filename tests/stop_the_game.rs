@@ -1,5 +1,5 @@
 use gstd::prelude::*;
-use gtest::System;
+use gtest::{Log, System};
 use rps_io::*;
 
 mod routines;
@@ -19,7 +19,7 @@ fn check_during_the_first_round() {
 
     USERS
         .iter()
-        .for_each(|user| sys.claim_value_from_mailbox(*user));
+        .for_each(|user| claim_winnings(&sys, &game, *user));
 
     USERS
         .iter()
@@ -83,7 +83,7 @@ fn check_not_all_players_in_progress_of_second_round() {
 
     USERS
         .iter()
-        .for_each(|user| sys.claim_value_from_mailbox(*user));
+        .for_each(|user| claim_winnings(&sys, &game, *user));
 
     rewarding_users.iter().for_each(|user| {
         check_users_balance(
@@ -108,5 +108,37 @@ fn check_not_owner_stop() {
     let moves = [Move::Lizard, Move::Paper, Move::Lizard, Move::Lizard];
     let game = reach_reveal_stage_with_init(&sys, USERS, &moves);
 
-    failure_stop_the_game(&game, USERS[1]);
+    failure_stop_the_game(&game, USERS[1], RpsError::NotOperator);
+}
+
+#[test]
+fn check_game_over_listener() {
+    let sys = System::new();
+    let game = init_and_register_with_users(&sys, COMMON_USERS_SET);
+    let listener = USERS[3];
+
+    let result = game.send(
+        USERS[1],
+        Action::SetGameOverListener(GAME_ID, Some(listener.into())),
+    );
+    assert!(result.contains(&(USERS[1], Err::<Event, _>(RpsError::NotAdmin).encode())));
+
+    let result = game.send(
+        USERS[0],
+        Action::SetGameOverListener(GAME_ID, Some(listener.into())),
+    );
+    assert!(result.contains(&(
+        USERS[0],
+        Ok::<_, RpsError>(Event::GameOverListenerSet).encode()
+    )));
+
+    check_stop_the_game(&game, USERS[0], COMMON_USERS_SET);
+
+    let notification = GameOverNotification {
+        game_id: GAME_ID,
+        winner: None,
+    };
+    assert!(sys
+        .get_mailbox(listener)
+        .contains(&Log::builder().dest(listener).payload(notification)));
 }
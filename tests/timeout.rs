@@ -9,24 +9,25 @@ fn timeout_on_preparation_without_users() {
     let sys = System::new();
     let game = common_init(&sys);
 
-    check_change_next_game_config(
+    check_change_current_game_config(
         &game,
         USERS[0],
         GameConfig {
             bet_size: 0,
-            players_count_limit: 2,
+            players_count_limit: 3,
             entry_timeout_ms: COMMON_TIMEOUT * 2,
             move_timeout_ms: COMMON_TIMEOUT * 3,
             reveal_timeout_ms: COMMON_TIMEOUT * 4,
+            ..COMMON_CONFIG
         },
     );
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
 
-    check_register_player(&game, USERS[0], COMMON_BET);
-    check_register_player(&game, USERS[1], COMMON_BET);
-    check_register_player(&game, USERS[2], COMMON_BET);
-    check_register_player(&game, USERS[3], COMMON_BET);
+    check_register_player(&game, USERS[0], 0);
+    check_register_player(&game, USERS[1], 0);
+    check_register_player(&game, USERS[2], 0);
+    failure_register_player(&game, USERS[3], 0, RpsError::LobbyIsFull);
 }
 
 #[test]
@@ -45,12 +46,18 @@ fn timeout_on_preparation_with_one_users() {
             entry_timeout_ms: COMMON_TIMEOUT * 2,
             move_timeout_ms: COMMON_TIMEOUT * 3,
             reveal_timeout_ms: COMMON_TIMEOUT * 4,
+            ..COMMON_CONFIG
         },
     );
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
 
-    failure_register_player(&game, USERS[0], COMMON_BET);
+    failure_register_player(
+        &game,
+        USERS[0],
+        COMMON_BET,
+        RpsError::PlayerAlreadyRegistered,
+    );
     check_register_player(&game, USERS[1], COMMON_BET);
     check_register_player(&game, USERS[2], COMMON_BET);
     check_register_player(&game, USERS[3], COMMON_BET);
@@ -73,13 +80,14 @@ fn timeout_on_preparation_with_two_users() {
             entry_timeout_ms: COMMON_TIMEOUT * 2,
             move_timeout_ms: COMMON_TIMEOUT * 3,
             reveal_timeout_ms: COMMON_TIMEOUT * 4,
+            ..COMMON_CONFIG
         },
     );
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
 
-    failure_register_player(&game, USERS[2], COMMON_BET);
-    failure_register_player(&game, USERS[3], COMMON_BET);
+    failure_register_player(&game, USERS[2], COMMON_BET, RpsError::GameIsInProgress);
+    failure_register_player(&game, USERS[3], COMMON_BET, RpsError::GameIsInProgress);
     check_user_move(&game, USERS[0], Move::Rock);
 }
 
@@ -88,7 +96,7 @@ fn timeout_on_move_stage_without_users() {
     let sys = System::new();
     let game = common_init_and_register(&sys);
 
-    failure_register_player(&game, USERS[3], COMMON_BET);
+    failure_register_player(&game, USERS[3], COMMON_BET, RpsError::GameIsInProgress);
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
 
@@ -150,7 +158,7 @@ fn timeout_on_reveal_with_one_users() {
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
 
-    failure_user_reveal(&game, USERS[1], Move::Rock);
+    failure_user_reveal(&game, USERS[1], Move::Rock, RpsError::NotRevealStage);
     check_register_player(&game, USERS[0], COMMON_BET);
     check_register_player(&game, USERS[1], COMMON_BET);
     check_register_player(&game, USERS[2], COMMON_BET);
@@ -170,9 +178,31 @@ fn timeout_on_reveal_with_two_users() {
 
     sys.spend_blocks(blocks_count(COMMON_TIMEOUT + 1));
 
-    failure_user_reveal(&game, USERS[2], Move::Rock);
+    failure_user_reveal(&game, USERS[2], Move::Rock, RpsError::NotRevealStage);
 
     check_user_move(&game, USERS[0], Move::Rock);
     check_user_move(&game, USERS[1], Move::Scissors);
-    failure_user_move(&game, USERS[2], Move::Scissors);
+    failure_user_move(&game, USERS[2], Move::Scissors, RpsError::PlayerNotInGame);
+}
+
+#[test]
+fn timeout_on_preparation_with_less_than_min_players() {
+    let sys = System::new();
+    let game = common_init_with_owner_and_config(
+        &sys,
+        USERS[0],
+        GameConfig {
+            min_players_to_start: 3,
+            ..COMMON_CONFIG
+        },
+    );
+
+    register_players(&game, &USERS[0..2], COMMON_BET);
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+
+    failure_user_move(&game, USERS[0], Move::Rock, RpsError::NotMoveStage);
+    check_register_player(&game, USERS[2], COMMON_BET);
+
+    sys.spend_blocks(blocks_count(COMMON_TIMEOUT / 1_000 + 1));
+    check_user_move(&game, USERS[0], Move::Rock);
 }
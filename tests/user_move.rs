@@ -39,7 +39,7 @@ fn check_not_added_user() {
 
     let game = common_init_and_register(&sys);
 
-    failure_user_move(&game, USERS[3], Move::Spock);
+    failure_user_move(&game, USERS[3], Move::Spock, RpsError::PlayerNotInGame);
 }
 
 #[test]
@@ -49,7 +49,7 @@ fn check_move_twice() {
     let game = common_init_and_register(&sys);
 
     check_user_move(&game, USERS[0], Move::Spock);
-    failure_user_move(&game, USERS[0], Move::Spock);
+    failure_user_move(&game, USERS[0], Move::Spock, RpsError::PlayerNotInGame);
 }
 
 #[test]
@@ -62,10 +62,10 @@ fn check_on_reveal_stage() {
     check_user_move(&game, USERS[1], Move::Spock);
     check_user_move(&game, USERS[2], Move::Spock);
 
-    failure_user_move(&game, USERS[0], Move::Spock);
-    failure_user_move(&game, USERS[1], Move::Spock);
-    failure_user_move(&game, USERS[2], Move::Spock);
-    failure_user_move(&game, USERS[3], Move::Spock);
+    failure_user_move(&game, USERS[0], Move::Spock, RpsError::NotMoveStage);
+    failure_user_move(&game, USERS[1], Move::Spock, RpsError::NotMoveStage);
+    failure_user_move(&game, USERS[2], Move::Spock, RpsError::NotMoveStage);
+    failure_user_move(&game, USERS[3], Move::Spock, RpsError::NotMoveStage);
 
     check_user_reveal_with_continue(&game, USERS[0], Move::Spock);
 }
@@ -78,15 +78,13 @@ fn check_move_in_second_round_without_bet() {
 
     play_round(&game, USERS, &moves).contains(&(
         *USERS.last().unwrap(),
-        Event::SuccessfulReveal(RevealResult::NextRoundStarted {
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::NextRoundStarted {
             players: USERS.iter().copied().map(|id| id.into()).collect(),
-        })
+        }))
         .encode(),
     ));
 
-    let result = try_to_move(&game, USERS[0], Move::Rock);
-
-    assert!(!result.main_failed());
+    check_user_move(&game, USERS[0], Move::Rock);
 }
 
 #[test]
@@ -97,13 +95,46 @@ fn check_move_in_second_round_with_bet() {
 
     play_round(&game, USERS, &moves).contains(&(
         *USERS.last().unwrap(),
-        Event::SuccessfulReveal(RevealResult::NextRoundStarted {
+        Ok::<_, RpsError>(Event::SuccessfulReveal(RevealResult::NextRoundStarted {
             players: USERS.iter().copied().map(|id| id.into()).collect(),
-        })
+        }))
         .encode(),
     ));
 
-    let result = try_to_move(&game, USERS[0], Move::Rock);
+    check_user_move(&game, USERS[0], Move::Rock);
+}
+
+#[test]
+fn check_wrong_hash_format() {
+    let sys = System::new();
+
+    let game = common_init_and_register(&sys);
+
+    let result = game.send(USERS[0], Action::MakeMove(GAME_ID, vec![0; 31]));
+
+    assert!(result.contains(&(
+        USERS[0],
+        Err::<Event, _>(RpsError::WrongMoveHashFormat).encode()
+    )));
+}
+
+#[test]
+fn check_copied_commitment() {
+    let sys = System::new();
+
+    let game = common_init_and_register(&sys);
+
+    check_user_move(&game, USERS[0], Move::Rock);
+
+    let real_move = Move::Rock.number().to_string() + DEFAULT_PASSWORD;
+    let copied_commitment = commitment(&game, USERS[0], real_move.as_bytes());
+    let result = game.send(
+        USERS[1],
+        Action::MakeMove(GAME_ID, copied_commitment.to_vec()),
+    );
 
-    assert!(!result.main_failed());
+    assert!(result.contains(&(
+        USERS[1],
+        Err::<Event, _>(RpsError::DuplicateCommitment).encode()
+    )));
 }